@@ -1,16 +1,25 @@
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use anyhow::Result;
-use clap::{arg, command, value_parser, ArgMatches, Arg, ArgAction};
+use clap::builder::PossibleValuesParser;
+use clap::{arg, command, value_parser, Arg, ArgAction, ArgMatches};
 
-use super::{Args, DriverType};
+use webdriver_downloader::prelude::{
+    DriverFactory, DriverRegistry, Platform, VerificationLevel, VerificationScenario,
+};
+use webdriver_downloader::traits::verification_scenario::{builtin_scenario, BUILTIN_SCENARIOS};
+
+use super::{Args, InstalledCheck};
 
 pub(super) fn get_args() -> Result<Args> {
+    let registry = DriverRegistry::default();
+
     let matches = command!()
         .arg(
             arg!(--type <TYPE> "driver type")
                 .default_value("chrome")
-                .value_parser(["chrome", "gecko"]),
+                .value_parser(PossibleValuesParser::new(registry.names().map(str::to_string))),
         )
         .arg(
             arg!(--driver <PATH> "driver path")
@@ -40,61 +49,111 @@ pub(super) fn get_args() -> Result<Args> {
         )
         .arg(arg!(--mkdir).help("make directory to driver path"))
         .arg(arg!(--reinstall).help("force reinstall even if driver is already installed"))
+        .arg(
+            Arg::new("installed-check")
+                .long("installed-check")
+                .default_value("full")
+                .value_parser(["exists", "status", "full", "offline"])
+                .help("how to check whether the driver is already installed. \"status\" starts the driver without the browser, \"offline\" only compares versions"),
+        )
+        .arg(
+            Arg::new("scenario")
+                .long("scenario")
+                .action(ArgAction::Append)
+                .value_delimiter(',')
+                .default_value("navigate")
+                .value_parser(BUILTIN_SCENARIOS)
+                .help("checks to verify the driver with, in order. Can be repeated or comma separated"),
+        )
+        .arg(
+            Arg::new("browser-arg")
+                .long("browser-arg")
+                .value_name("ARG")
+                .action(ArgAction::Append)
+                .allow_hyphen_values(true)
+                .help("extra argument to launch the browser with during verification. Can be repeated"),
+        )
+        .arg(arg!(--headed).help("verify with a visible browser window instead of headless"))
+        .arg(
+            arg!(--platform <PLATFORM>)
+                .value_parser(value_parser!(Platform))
+                .help("platform to download the driver for, such as linux-x86_64, macos-aarch64 or windows-x86_64. Defaults to the current platform. Drivers for other platforms are not verified"),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("print what would be installed without downloading or installing"),
+        )
         .get_matches();
 
-    let driver_type = get_driver_type(&matches);
-    let driver_install_path = get_driver_install_path(&matches, driver_type)?;
-    let browser_path = get_browser_path(&matches, driver_type)?;
+    let driver_factory = get_driver_factory(&matches, &registry);
+    let platform = get_platform(&matches);
+    let driver_install_path = get_driver_install_path(&matches, driver_factory.as_ref(), platform)?;
+    let browser_path = get_browser_path(&matches, driver_factory.as_ref())?;
     let num_tries = get_num_tries(&matches);
     let skip_verification = get_skip_verification(&matches);
 
     let mkdir = get_mkdir(&matches);
     let reinstall = get_reinstall(&matches);
+    let installed_check = get_installed_check(&matches);
+    let scenarios = get_scenarios(&matches);
+    let browser_args = get_browser_args(&matches);
+    let headed = get_headed(&matches);
+    let dry_run = get_dry_run(&matches);
 
     Ok(Args {
-        driver_type,
+        driver_factory,
         driver_install_path,
         browser_path,
         num_tries,
         skip_verification,
         mkdir,
         reinstall,
+        installed_check,
+        scenarios,
+        browser_args,
+        headed,
+        platform,
+        dry_run,
     })
 }
 
-fn get_driver_type(matches: &ArgMatches) -> DriverType {
-    let browser_type = matches
+fn get_driver_factory(matches: &ArgMatches, registry: &DriverRegistry) -> Arc<dyn DriverFactory> {
+    let driver_type = matches
         .get_one::<String>("type")
         .expect("\"type\" arg is empty");
 
-    match browser_type.as_str() {
-        "chrome" => DriverType::Chrome,
-        "gecko" => DriverType::Gecko,
-        _ => panic!("Unexpected argument value of \"type\". {:?}", browser_type),
-    }
+    registry
+        .get(driver_type)
+        .unwrap_or_else(|| panic!("Unexpected argument value of \"type\". {:?}", driver_type))
 }
 
-fn get_driver_install_path(matches: &ArgMatches, driver_type: DriverType) -> Result<PathBuf> {
+fn get_driver_install_path(
+    matches: &ArgMatches,
+    driver_factory: &dyn DriverFactory,
+    platform: Platform,
+) -> Result<PathBuf> {
     let driver_install_path = matches
         .get_one::<PathBuf>("driver")
         .expect("\"driver\" arg is empty");
 
     if driver_install_path == Path::new("-") {
-        driver_type
-            .default_driver_install_path()
+        driver_factory
+            .default_driver_path(platform)
             .map_err(|e| e.into())
     } else {
         Ok(driver_install_path.clone())
     }
 }
 
-fn get_browser_path(matches: &ArgMatches, driver_type: DriverType) -> Result<PathBuf> {
+fn get_browser_path(matches: &ArgMatches, driver_factory: &dyn DriverFactory) -> Result<PathBuf> {
     let browser_path = matches
         .get_one::<PathBuf>("browser")
         .expect("\"browser\" arg is empty");
 
     if browser_path == Path::new("-") {
-        driver_type.default_browser_path().map_err(|e| e.into())
+        driver_factory.default_browser_path().map_err(|e| e.into())
     } else {
         Ok(browser_path.clone())
     }
@@ -117,3 +176,53 @@ fn get_mkdir(matches: &ArgMatches) -> bool {
 fn get_reinstall(matches: &ArgMatches) -> bool {
     matches.get_flag("reinstall")
 }
+
+fn get_installed_check(matches: &ArgMatches) -> InstalledCheck {
+    let installed_check = matches
+        .get_one::<String>("installed-check")
+        .expect("\"installed-check\" arg is empty");
+
+    match installed_check.as_str() {
+        "exists" => InstalledCheck::Level(VerificationLevel::Exists),
+        "status" => InstalledCheck::Level(VerificationLevel::Status),
+        "full" => InstalledCheck::Level(VerificationLevel::Full),
+        "offline" => InstalledCheck::Offline,
+        _ => panic!(
+            "Unexpected argument value of \"installed-check\". {:?}",
+            installed_check
+        ),
+    }
+}
+
+fn get_scenarios(matches: &ArgMatches) -> Vec<Arc<dyn VerificationScenario>> {
+    matches
+        .get_many::<String>("scenario")
+        .expect("\"scenario\" arg is empty")
+        .map(|name| {
+            builtin_scenario(name)
+                .unwrap_or_else(|| panic!("Unexpected argument value of \"scenario\". {:?}", name))
+        })
+        .collect()
+}
+
+fn get_browser_args(matches: &ArgMatches) -> Vec<String> {
+    matches
+        .get_many::<String>("browser-arg")
+        .map(|args| args.cloned().collect())
+        .unwrap_or_default()
+}
+
+fn get_headed(matches: &ArgMatches) -> bool {
+    matches.get_flag("headed")
+}
+
+fn get_platform(matches: &ArgMatches) -> Platform {
+    matches
+        .get_one::<Platform>("platform")
+        .copied()
+        .unwrap_or_default()
+}
+
+fn get_dry_run(matches: &ArgMatches) -> bool {
+    matches.get_flag("dry-run")
+}
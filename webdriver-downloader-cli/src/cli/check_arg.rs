@@ -1,8 +1,9 @@
 use std::fs;
 
 use anyhow::{bail, Context, Result};
+use webdriver_downloader::prelude::*;
 
-use crate::cli::Args;
+use crate::cli::{Args, InstalledCheck};
 
 pub(crate) fn check_driver_install_path(args: &Args) -> Result<()> {
     let parent = args
@@ -11,6 +12,10 @@ pub(crate) fn check_driver_install_path(args: &Args) -> Result<()> {
         .with_context(|| "Failed to get parent directory of driver install path.")?;
 
     if args.mkdir {
+        if args.dry_run {
+            return Ok(());
+        }
+
         fs::create_dir_all(parent)
             .with_context(|| "Failed to create parent directory of driver install path.")?;
     }
@@ -41,3 +46,14 @@ pub(crate) fn check_tries(args: &Args) -> Result<()> {
         bail!("Number of tries must be greater than 0.")
     }
 }
+
+pub(crate) fn check_installed_check(
+    installed_check: InstalledCheck,
+    driver_info: &dyn RegisteredDriver,
+) -> Result<()> {
+    if matches!(installed_check, InstalledCheck::Offline) && driver_info.as_offline().is_none() {
+        bail!("Driver does not support \"--installed-check offline\".")
+    }
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::sync::Arc;
 
 pub use run::run;
 use webdriver_downloader::prelude::*;
@@ -9,41 +10,25 @@ mod run;
 
 #[derive(Debug)]
 pub(crate) struct Args {
-    pub driver_type: DriverType,
+    pub driver_factory: Arc<dyn DriverFactory>,
     pub driver_install_path: PathBuf,
     pub browser_path: PathBuf,
     pub num_tries: usize,
     pub skip_verification: bool,
     pub mkdir: bool,
     pub reinstall: bool,
+    pub installed_check: InstalledCheck,
+    pub scenarios: Vec<Arc<dyn VerificationScenario>>,
+    pub browser_args: Vec<String>,
+    pub headed: bool,
+    pub platform: Platform,
+    pub dry_run: bool,
 }
 
+/// How to check whether the driver is already installed.
 #[derive(Debug, Copy, Clone)]
-pub(crate) enum DriverType {
-    Chrome,
-    Gecko,
-}
-
-impl DriverType {
-    fn default_driver_install_path(&self) -> Result<PathBuf, os_specific::DefaultPathError> {
-        match self {
-            DriverType::Chrome => os_specific::chromedriver::default_driver_path(),
-            DriverType::Gecko => os_specific::geckodriver::default_driver_path(),
-        }
-    }
-
-    fn default_browser_path(&self) -> Result<PathBuf, os_specific::DefaultPathError> {
-        match self {
-            DriverType::Chrome => {
-                let path_chrome_for_testing =
-                    os_specific::chromedriver_for_testing::default_browser_path()?;
-                if path_chrome_for_testing.exists() {
-                    Ok(path_chrome_for_testing)
-                } else {
-                    os_specific::chromedriver_old::default_browser_path()
-                }
-            }
-            DriverType::Gecko => os_specific::geckodriver::default_browser_path(),
-        }
-    }
+pub(crate) enum InstalledCheck {
+    Level(VerificationLevel),
+    /// Compare driver and browser versions without starting either.
+    Offline,
 }
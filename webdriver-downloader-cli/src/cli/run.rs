@@ -1,4 +1,4 @@
-use webdriver_downloader::driver_impls::chromedriver_info::ChromedriverInfo;
+use std::fmt::Write;
 
 use super::build_arg::*;
 use super::check_arg::*;
@@ -11,41 +11,49 @@ pub async fn run() -> anyhow::Result<String> {
     check_browser(&args)?;
     check_tries(&args)?;
 
-    let install_result = match args.driver_type {
-        DriverType::Chrome => {
-            let driver_info = ChromedriverInfo::new(args.driver_install_path, args.browser_path);
-
-            install(
-                &driver_info,
-                args.reinstall,
-                args.skip_verification,
-                args.num_tries,
-            )
-            .await
-        }
-        DriverType::Gecko => {
-            let driver_info = GeckodriverInfo::new(args.driver_install_path, args.browser_path);
-
-            install(
-                &driver_info,
-                args.reinstall,
-                args.skip_verification,
-                args.num_tries,
-            )
-            .await
-        }
+    let browser_options = BrowserOptions {
+        headless: !args.headed,
+        args: args.browser_args,
+        ..Default::default()
     };
 
-    install_result.map_err(|e| e.into())
+    let driver_info = args.driver_factory.build(DriverOptions {
+        driver_install_path: args.driver_install_path,
+        browser_path: args.browser_path,
+        verification_scenarios: args.scenarios,
+        browser_options,
+        platform: args.platform,
+    });
+
+    check_installed_check(args.installed_check, driver_info.as_ref())?;
+
+    install(
+        driver_info.as_ref(),
+        args.reinstall,
+        args.installed_check,
+        args.skip_verification,
+        args.num_tries,
+        args.dry_run,
+    )
+    .await
+    .map_err(|e| e.into())
 }
 
 async fn install(
-    driver_info: &impl WebdriverDownloadInfo,
+    driver_info: &dyn RegisteredDriver,
     reinstall: bool,
+    installed_check: InstalledCheck,
     skip_verification: bool,
     num_tries: usize,
+    dry_run: bool,
 ) -> Result<String, WebdriverDownloadError> {
-    if !reinstall && driver_info.is_installed().await {
+    if dry_run {
+        // download_install only ever tries the first candidate.
+        let max_tries = if skip_verification { 1 } else { num_tries };
+        let plan = driver_info.plan(max_tries).await?;
+
+        Ok(format_plan(&plan))
+    } else if !reinstall && is_installed(driver_info, installed_check).await {
         Ok("Driver already installed.".to_string())
     } else {
         if skip_verification {
@@ -57,3 +65,75 @@ async fn install(
         Ok("Driver installed successfully.".to_string())
     }
 }
+
+async fn is_installed(driver_info: &dyn RegisteredDriver, installed_check: InstalledCheck) -> bool {
+    match installed_check {
+        InstalledCheck::Level(level) => driver_info.is_installed_with(level).await,
+        InstalledCheck::Offline => driver_info
+            .as_offline()
+            .is_some_and(|driver_info| driver_info.verify_driver_offline().is_ok()),
+    }
+}
+
+fn format_plan(plan: &InstallationPlan) -> String {
+    let mut output = String::new();
+
+    let browser_path = plan
+        .browser_path
+        .as_ref()
+        .map_or("unknown".to_string(), |path| path.display().to_string());
+    let browser_version = plan
+        .browser_version
+        .as_ref()
+        .map_or("unknown".to_string(), |version| version.to_string());
+
+    writeln!(
+        output,
+        "Browser: {} (version {})",
+        browser_path, browser_version
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "Driver install path: {}",
+        plan.driver_install_path.display()
+    )
+    .unwrap();
+
+    match plan.first_candidate() {
+        Some(first) => writeln!(
+            output,
+            "Would try version {} first: {}",
+            first.webdriver_version, first.url
+        )
+        .unwrap(),
+        None => writeln!(output, "No candidate versions found.").unwrap(),
+    }
+
+    if plan
+        .version_urls
+        .iter()
+        .any(|version_url| version_url.stale)
+    {
+        writeln!(
+            output,
+            "Version sources were unreachable, candidates are from bundled metadata."
+        )
+        .unwrap();
+    }
+
+    writeln!(output, "Candidates:").unwrap();
+    for (index, version_url) in plan.version_urls.iter().enumerate() {
+        writeln!(
+            output,
+            "  {}. {} ({}): {}",
+            index + 1,
+            version_url.webdriver_version,
+            version_url.version_req,
+            version_url.url
+        )
+        .unwrap();
+    }
+
+    output.trim_end().to_string()
+}
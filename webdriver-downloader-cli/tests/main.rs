@@ -108,6 +108,35 @@ async fn test_existing_driver() {
         .assert(predicate::path::exists());
 }
 
+/// Test for the case where the driver is already installed and only its status is checked.
+#[tokio::test]
+async fn test_existing_driver_status_check() {
+    let (temp_dir, driver_path) = download_driver_to_temp_dir();
+
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+    let mut cmd = Command::cargo_bin("webdriver-downloader").unwrap();
+    let assert = cmd
+        .args(
+            [
+                OsStr::new("--driver"),
+                driver_path.as_os_str(),
+                OsStr::new("--installed-check"),
+                OsStr::new("status"),
+            ]
+            .iter(),
+        )
+        .assert();
+
+    assert
+        .success()
+        .stdout(contains("Driver already installed.\n"));
+
+    temp_dir
+        .child(CHROMEDRIVER_BIN)
+        .assert(predicate::path::exists());
+}
+
 /// Test for the case where the driver is already installed and reinstall flag is set.
 #[tokio::test]
 async fn test_reinstall() {
@@ -136,8 +165,68 @@ async fn test_reinstall() {
         .assert(predicate::path::exists());
 }
 
+/// Test for dry-run flag
+#[test]
+fn test_dry_run() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let mut driver_path = temp_dir.to_path_buf();
+    driver_path.push("new_dir");
+    driver_path.push(CHROMEDRIVER_BIN);
+
+    let mut cmd = Command::cargo_bin("webdriver-downloader").unwrap();
+    let assert = cmd
+        .args(
+            [
+                OsStr::new("--driver"),
+                driver_path.as_os_str(),
+                OsStr::new("--mkdir"),
+                OsStr::new("--dry-run"),
+            ]
+            .iter(),
+        )
+        .assert();
+
+    assert.success().stdout(contains("Candidates:"));
+    temp_dir.child("new_dir").assert(predicate::path::missing());
+}
+
+/// Test for platform arg
+#[test]
+fn test_dry_run_other_platform() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let mut driver_path = temp_dir.to_path_buf();
+    driver_path.push("chromedriver.exe");
+
+    let mut cmd = Command::cargo_bin("webdriver-downloader").unwrap();
+    let assert = cmd
+        .args(
+            [
+                OsStr::new("--driver"),
+                driver_path.as_os_str(),
+                OsStr::new("--platform"),
+                OsStr::new("windows-x86_64"),
+                OsStr::new("--dry-run"),
+            ]
+            .iter(),
+        )
+        .assert();
+
+    assert.success().stdout(contains("win"));
+}
+
 // Testcases for failures
 
+/// Test for unknown platform
+#[test]
+fn test_fails_unknown_platform() {
+    let mut cmd = Command::cargo_bin("webdriver-downloader").unwrap();
+    let assert = cmd
+        .args(["--platform", "beos-x86_64", "--dry-run"])
+        .assert();
+
+    assert.failure();
+}
+
 /// Test for num_tries
 #[test]
 fn test_fails_0_tries() {
@@ -0,0 +1,208 @@
+//! Regenerates the version metadata snapshots bundled with the crate.
+//! Run before each release with `cargo run --example update_snapshots`.
+//!
+//! Set `GITHUB_TOKEN` to avoid the GitHub api rate limit.
+//!
+//! The snapshots are only a fallback, so they are trimmed to keep the crate small:
+//! chromedriver snapshots keep the newest version of each major version,
+//! and the geckodriver snapshot keeps the newest [`GECKODRIVER_RELEASES`] stable releases.
+//! geckodriver's support table is kept whole, since it is small and old releases need their rows.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use regex::Regex;
+use reqwest::header;
+use serde_json::{json, Value};
+
+use webdriver_downloader::driver_impls::geckodriver_info::GITHUB_TOKEN_ENV;
+use webdriver_downloader::driver_impls::{
+    chromedriver_for_testing_info, chromedriver_old_info, geckodriver_info,
+};
+
+/// Number of stable geckodriver releases kept in the snapshot.
+const GECKODRIVER_RELEASES: usize = 8;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let driver_impls = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/driver_impls");
+
+    update_chromedriver_for_testing(&driver_impls).await?;
+    update_chromedriver_old(&driver_impls).await?;
+    update_geckodriver(&driver_impls).await?;
+    update_geckodriver_support_table(&driver_impls).await?;
+
+    Ok(())
+}
+
+/// Keeps only the chromedriver downloads of the newest version of each major version.
+async fn update_chromedriver_for_testing(driver_impls: &Path) -> Result<()> {
+    let root: Value = serde_json::from_str(
+        &reqwest::get(chromedriver_for_testing_info::VERSIONS_URL)
+            .await?
+            .error_for_status()?
+            .text()
+            .await?,
+    )?;
+
+    let versions = root["versions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|version| version["downloads"]["chromedriver"].is_array())
+        .filter_map(|version| {
+            let version_str = version["version"].as_str()?;
+            Some((version_str, version))
+        });
+    let versions: Vec<Value> = newest_per_major(versions)
+        .into_iter()
+        .map(|version| {
+            json!({
+                "version": version["version"],
+                "downloads": {"chromedriver": version["downloads"]["chromedriver"]},
+            })
+        })
+        .collect();
+
+    let snapshot = json!({"timestamp": root["timestamp"], "versions": versions});
+    write_snapshot(
+        driver_impls.join("chromedriver_for_testing_info/snapshot.json"),
+        serde_json::to_string_pretty(&snapshot)?,
+    )
+}
+
+/// Keeps only the keys of the chromedriver zip files of the newest version of each major version.
+async fn update_chromedriver_old(driver_impls: &Path) -> Result<()> {
+    let xml = reqwest::get(chromedriver_old_info::VERSIONS_URL)
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let re = Regex::new(r"<Key>([0-9.]+)/chromedriver_[a-z0-9_]+\.zip</Key>")?;
+    let versions = re.captures_iter(&xml).map(|captures| {
+        let version = captures.get(1).unwrap().as_str();
+        (version, version)
+    });
+    let newest = newest_per_major(versions);
+    let mut snapshot = vec![
+        "<?xml version='1.0' encoding='UTF-8'?>".to_string(),
+        r#"<ListBucketResult xmlns="http://doc.s3.amazonaws.com/2006-03-01">"#.to_string(),
+        "<Name>chromedriver</Name>".to_string(),
+    ];
+    snapshot.extend(
+        re.captures_iter(&xml)
+            .filter(|captures| newest.contains(&captures.get(1).unwrap().as_str()))
+            .map(|captures| format!("<Contents>{}</Contents>", captures.get(0).unwrap().as_str())),
+    );
+    snapshot.push("</ListBucketResult>".to_string());
+
+    write_snapshot(
+        driver_impls.join("chromedriver_old_info/snapshot.xml"),
+        snapshot.join("\n"),
+    )
+}
+
+/// Keeps only the fields used to pick a release asset, for the newest stable releases.
+async fn update_geckodriver(driver_impls: &Path) -> Result<()> {
+    let mut request = reqwest::Client::new()
+        .get(geckodriver_info::RELEASES_URL)
+        .header(header::ACCEPT, "application/vnd.github+json")
+        .header(header::USER_AGENT, "webdriver-downloader");
+    if let Ok(token) = std::env::var(GITHUB_TOKEN_ENV) {
+        request = request.bearer_auth(token);
+    }
+
+    let releases: Value =
+        serde_json::from_str(&request.send().await?.error_for_status()?.text().await?)?;
+
+    let snapshot: Vec<Value> = releases
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|release| release["draft"] == false && release["prerelease"] == false)
+        .take(GECKODRIVER_RELEASES)
+        .map(|release| {
+            let assets: Vec<Value> = release["assets"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|asset| {
+                    json!({
+                        "name": asset["name"],
+                        "browser_download_url": asset["browser_download_url"],
+                    })
+                })
+                .collect();
+
+            json!({
+                "tag_name": release["tag_name"],
+                "draft": release["draft"],
+                "prerelease": release["prerelease"],
+                "assets": assets,
+            })
+        })
+        .collect();
+
+    write_snapshot(
+        driver_impls.join("geckodriver_info/snapshot.json"),
+        serde_json::to_string_pretty(&snapshot)?,
+    )
+}
+
+/// Keeps only the table of supported Firefox versions from geckodriver's `Support.md`.
+async fn update_geckodriver_support_table(driver_impls: &Path) -> Result<()> {
+    let support_md = reqwest::get(geckodriver_info::SUPPORT_TABLE_URL)
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let lines: Vec<&str> = support_md.lines().collect();
+    let start = lines.iter().position(|line| *line == "<table>");
+    let end = lines.iter().position(|line| *line == "</table>");
+    let (Some(start), Some(end)) = (start, end) else {
+        bail!(
+            "No table found in {}, update the bundled copy by hand",
+            geckodriver_info::SUPPORT_TABLE_URL
+        );
+    };
+
+    write_snapshot(
+        driver_impls.join("geckodriver_info/support_table.md"),
+        lines[start..=end].join("\n"),
+    )
+}
+
+/// The item with the newest version of each major version, ordered by version.
+/// Versions are dot separated numbers, such as `120.0.6099.109`.
+fn newest_per_major<'a, T>(items: impl IntoIterator<Item = (&'a str, T)>) -> Vec<T> {
+    let numbers = |version: &str| -> Vec<u64> {
+        version
+            .split('.')
+            .map(|number| number.parse().unwrap_or(0))
+            .collect()
+    };
+
+    let mut newest: BTreeMap<u64, (Vec<u64>, T)> = BTreeMap::new();
+    for (version, item) in items {
+        let version = numbers(version);
+        let major = version.first().copied().unwrap_or(0);
+        if newest
+            .get(&major)
+            .is_none_or(|(newest_version, _)| &version > newest_version)
+        {
+            newest.insert(major, (version, item));
+        }
+    }
+
+    newest.into_values().map(|(_, item)| item).collect()
+}
+
+fn write_snapshot(path: PathBuf, contents: String) -> Result<()> {
+    println!("Writing {}", path.display());
+    std::fs::write(path, contents + "\n")?;
+
+    Ok(())
+}
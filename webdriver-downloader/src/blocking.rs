@@ -0,0 +1,80 @@
+//! Synchronous API for code that does not run a tokio runtime, such as build scripts.
+//!
+//! Requires the `blocking` feature.
+//!
+//! ```no_run
+//! use webdriver_downloader::blocking::BlockingDriver;
+//! use webdriver_downloader::prelude::*;
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let driver = BlockingDriver::new(ChromedriverInfo::new_default()?)?;
+//!
+//!     if !driver.is_installed() {
+//!         driver.download_verify_install(5)?;
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::io;
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::traits::offline_verification_info::{OfflineVerificationError, OfflineVerificationInfo};
+use crate::traits::webdriver_download_info::{WebdriverDownloadError, WebdriverDownloadInfo};
+
+/// Driver info with its own current-thread tokio runtime, which the async methods are run on.
+///
+/// # Panics
+///
+/// The methods panic if called from within an async runtime. Use the async API there instead.
+#[derive(Debug)]
+pub struct BlockingDriver<T> {
+    info: T,
+    runtime: Runtime,
+}
+
+impl<T> BlockingDriver<T> {
+    /// # Errors
+    ///
+    /// Returns an error if the runtime cannot be created.
+    pub fn new(info: T) -> io::Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+
+        Ok(BlockingDriver { info, runtime })
+    }
+
+    pub fn info(&self) -> &T {
+        &self.info
+    }
+
+    pub fn into_inner(self) -> T {
+        self.info
+    }
+}
+
+impl<T: WebdriverDownloadInfo> BlockingDriver<T> {
+    /// Blocking [`WebdriverDownloadInfo::is_installed`].
+    pub fn is_installed(&self) -> bool {
+        self.runtime.block_on(self.info.is_installed())
+    }
+
+    /// Blocking [`WebdriverDownloadInfo::download_install`].
+    pub fn download_install(&self) -> Result<(), WebdriverDownloadError> {
+        self.runtime.block_on(self.info.download_install())
+    }
+
+    /// Blocking [`WebdriverDownloadInfo::download_verify_install`].
+    pub fn download_verify_install(&self, max_tries: usize) -> Result<(), WebdriverDownloadError> {
+        self.runtime
+            .block_on(self.info.download_verify_install(max_tries))
+    }
+}
+
+impl<T: OfflineVerificationInfo> BlockingDriver<T> {
+    /// [`OfflineVerificationInfo::verify_driver_offline`], which does not need the runtime.
+    pub fn verify_driver_offline(&self) -> Result<(), OfflineVerificationError> {
+        self.info.verify_driver_offline()
+    }
+}
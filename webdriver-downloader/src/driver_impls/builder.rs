@@ -0,0 +1,265 @@
+//! Builder for the driver infos, such as [`ChromedriverInfo::builder`](super::chromedriver_info::ChromedriverInfo::builder).
+//!
+//! Unset paths default to the driver's [`os_specific`](crate::os_specific) locations when [`DriverInfoBuilder::build`] is called.
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::metadata_cache::MetadataCache;
+use crate::os_specific::DefaultPathError;
+use crate::platform::Platform;
+use crate::traits::verification_info::{BrowserOptions, VerificationConfig, VerificationTarget};
+use crate::traits::verification_scenario::{default_scenarios, VerificationScenario};
+
+/// Errors that can occur when building a driver info.
+#[derive(thiserror::Error, Debug)]
+pub enum BuildError {
+    #[error(transparent)]
+    DefaultPath(#[from] DefaultPathError),
+    #[error("Driver install path {0} has no file name")]
+    NoFileName(PathBuf),
+    #[error("Driver install path {0} is a directory")]
+    InstallPathIsDirectory(PathBuf),
+    #[error("No verification scenarios, verification would not check anything")]
+    NoVerificationScenarios,
+    #[error("Verification poll interval must not be zero")]
+    ZeroPollInterval,
+}
+
+/// Driver infos that [`DriverInfoBuilder`] can build.
+pub trait BuildDriverInfo: Sized {
+    /// Settings only this driver has, such as geckodriver's GitHub token.
+    type Extra: Debug + Default;
+
+    /// Used if [`DriverInfoBuilder::driver_install_path`] is not set.
+    fn default_driver_path(platform: Platform) -> Result<PathBuf, DefaultPathError>;
+
+    /// Used if [`DriverInfoBuilder::browser_path`] is not set.
+    fn default_browser_path() -> Result<PathBuf, DefaultPathError>;
+
+    fn from_settings(settings: DriverInfoSettings, extra: Self::Extra) -> Self;
+}
+
+/// Validated settings of a [`DriverInfoBuilder`].
+#[derive(Debug)]
+pub struct DriverInfoSettings {
+    pub driver_install_path: PathBuf,
+    pub browser_path: PathBuf,
+    pub verification_target: VerificationTarget,
+    pub verification_config: VerificationConfig,
+    pub verification_scenarios: Vec<Arc<dyn VerificationScenario>>,
+    pub browser_options: BrowserOptions,
+    pub metadata_cache: MetadataCache,
+    pub platform: Platform,
+}
+
+/// Builds a driver info of type `T`. Every setting has the same default as `T::new`.
+#[derive(Debug)]
+pub struct DriverInfoBuilder<T: BuildDriverInfo> {
+    driver_install_path: Option<PathBuf>,
+    browser_path: Option<PathBuf>,
+    verification_target: VerificationTarget,
+    verification_config: VerificationConfig,
+    verification_scenarios: Vec<Arc<dyn VerificationScenario>>,
+    browser_options: BrowserOptions,
+    metadata_cache: MetadataCache,
+    platform: Platform,
+    pub(crate) extra: T::Extra,
+    info: PhantomData<T>,
+}
+
+impl<T: BuildDriverInfo> Default for DriverInfoBuilder<T> {
+    fn default() -> Self {
+        DriverInfoBuilder {
+            driver_install_path: None,
+            browser_path: None,
+            verification_target: VerificationTarget::default(),
+            verification_config: VerificationConfig::default(),
+            verification_scenarios: default_scenarios(),
+            browser_options: BrowserOptions::default(),
+            metadata_cache: MetadataCache::default(),
+            platform: Platform::current(),
+            extra: T::Extra::default(),
+            info: PhantomData,
+        }
+    }
+}
+
+impl<T: BuildDriverInfo> DriverInfoBuilder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Path to install the driver to. Defaults to [`BuildDriverInfo::default_driver_path`].
+    pub fn driver_install_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.driver_install_path = Some(path.into());
+        self
+    }
+
+    /// Path to the browser binary. Defaults to [`BuildDriverInfo::default_browser_path`].
+    pub fn browser_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.browser_path = Some(path.into());
+        self
+    }
+
+    pub fn verification_target(mut self, target: VerificationTarget) -> Self {
+        self.verification_target = target;
+        self
+    }
+
+    pub fn verification_config(mut self, config: VerificationConfig) -> Self {
+        self.verification_config = config;
+        self
+    }
+
+    pub fn verification_scenarios(mut self, scenarios: Vec<Arc<dyn VerificationScenario>>) -> Self {
+        self.verification_scenarios = scenarios;
+        self
+    }
+
+    pub fn browser_options(mut self, options: BrowserOptions) -> Self {
+        self.browser_options = options;
+        self
+    }
+
+    pub fn metadata_cache(mut self, cache: MetadataCache) -> Self {
+        self.metadata_cache = cache;
+        self
+    }
+
+    /// Platform to download the driver for. Defaults to [`Platform::current`].
+    pub fn platform(mut self, platform: Platform) -> Self {
+        self.platform = platform;
+        self
+    }
+
+    /// Fills in default paths and validates the settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError::DefaultPath`] if a default path cannot be determined,
+    /// or another [`BuildError`] if the settings can't work.
+    pub fn build(self) -> Result<T, BuildError> {
+        let driver_install_path = match self.driver_install_path {
+            Some(path) => path,
+            None => T::default_driver_path(self.platform)?,
+        };
+        let browser_path = match self.browser_path {
+            Some(path) => path,
+            None => T::default_browser_path()?,
+        };
+
+        if driver_install_path.file_name().is_none() {
+            return Err(BuildError::NoFileName(driver_install_path));
+        }
+        if driver_install_path.is_dir() {
+            return Err(BuildError::InstallPathIsDirectory(driver_install_path));
+        }
+        if self.verification_scenarios.is_empty() {
+            return Err(BuildError::NoVerificationScenarios);
+        }
+        if self.verification_config.poll_interval == Duration::ZERO {
+            return Err(BuildError::ZeroPollInterval);
+        }
+
+        let settings = DriverInfoSettings {
+            driver_install_path,
+            browser_path,
+            verification_target: self.verification_target,
+            verification_config: self.verification_config,
+            verification_scenarios: self.verification_scenarios,
+            browser_options: self.browser_options,
+            metadata_cache: self.metadata_cache,
+            platform: self.platform,
+        };
+
+        Ok(T::from_settings(settings, self.extra))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use crate::driver_impls::chromedriver_for_testing_info::ChromedriverForTestingInfo;
+    use crate::driver_impls::geckodriver_info::GeckodriverInfo;
+    use crate::platform::{Arch, Os, Platform};
+    use crate::traits::verification_info::VerificationConfig;
+
+    use super::BuildError;
+
+    #[test]
+    fn builds_with_settings() {
+        let info = GeckodriverInfo::builder()
+            .driver_install_path("/opt/drivers/geckodriver")
+            .browser_path("/opt/firefox/firefox")
+            .platform(Platform::new(Os::Windows, Arch::X86_64))
+            .github_token("token")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            info.driver_install_path,
+            PathBuf::from("/opt/drivers/geckodriver")
+        );
+        assert_eq!(info.browser_path, PathBuf::from("/opt/firefox/firefox"));
+        assert_eq!(info.platform, Platform::new(Os::Windows, Arch::X86_64));
+        assert_eq!(info.github_token.unwrap().0, "token");
+        assert_eq!(info.verification_scenarios.len(), 1);
+    }
+
+    #[test]
+    fn names_default_driver_path_for_platform() {
+        let builder = || GeckodriverInfo::builder().browser_path("/opt/firefox/firefox");
+
+        let windows = builder()
+            .platform(Platform::new(Os::Windows, Arch::X86_64))
+            .build()
+            .unwrap();
+        let linux = builder()
+            .platform(Platform::new(Os::Linux, Arch::X86_64))
+            .build()
+            .unwrap();
+
+        assert!(windows.driver_install_path.ends_with("bin/geckodriver.exe"));
+        assert!(linux.driver_install_path.ends_with("bin/geckodriver"));
+    }
+
+    #[test]
+    fn validates_settings() {
+        let builder = || {
+            ChromedriverForTestingInfo::builder()
+                .driver_install_path("/opt/drivers/chromedriver")
+                .browser_path("/opt/chrome/chrome")
+        };
+
+        assert!(builder().build().is_ok());
+        assert!(matches!(
+            builder().verification_scenarios(vec![]).build(),
+            Err(BuildError::NoVerificationScenarios)
+        ));
+        assert!(matches!(
+            builder()
+                .verification_config(VerificationConfig {
+                    poll_interval: Duration::ZERO,
+                    ..Default::default()
+                })
+                .build(),
+            Err(BuildError::ZeroPollInterval)
+        ));
+        assert!(matches!(
+            builder().driver_install_path("/").build(),
+            Err(BuildError::NoFileName(_))
+        ));
+
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            builder().driver_install_path(dir.path()).build(),
+            Err(BuildError::InstallPathIsDirectory(_))
+        ));
+    }
+}
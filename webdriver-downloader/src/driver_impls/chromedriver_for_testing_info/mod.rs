@@ -1,17 +1,36 @@
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use crate::driver_impls::chromedriver_info::OfflineVerificationError;
+use crate::driver_impls::builder::DriverInfoBuilder;
+use crate::metadata_cache::MetadataCache;
 use crate::os_specific;
 use crate::os_specific::DefaultPathError;
-use crate::prelude::VersionReqUrlInfo;
+use crate::platform::Platform;
+use crate::traits::verification_info::{BrowserOptions, VerificationConfig, VerificationTarget};
+use crate::traits::verification_scenario::{default_scenarios, VerificationScenario};
 
 mod trait_impls;
 
+/// Known good Chrome for Testing versions, with their downloads.
+pub const VERSIONS_URL: &str =
+    "https://googlechromelabs.github.io/chrome-for-testing/known-good-versions-with-downloads.json";
+
+/// Snapshot of [`VERSIONS_URL`], trimmed to the newest version of each major version. Used if it is unreachable.
+/// Regenerate with `cargo run --example update_snapshots`.
+const VERSIONS_SNAPSHOT: &str = include_str!("snapshot.json");
+
 /// Information required to implement [WebdriverDownloadInfo](crate::prelude::WebdriverDownloadInfo) for Chrome for Testing.
 #[derive(Debug)]
 pub struct ChromedriverForTestingInfo {
     pub driver_install_path: PathBuf,
     pub browser_path: PathBuf,
+    pub verification_target: VerificationTarget,
+    pub verification_config: VerificationConfig,
+    pub verification_scenarios: Vec<Arc<dyn VerificationScenario>>,
+    pub browser_options: BrowserOptions,
+    /// Cache for the version metadata downloaded by [`driver_version_urls`](crate::prelude::VersionReqUrlInfo::driver_version_urls).
+    pub metadata_cache: MetadataCache,
+    pub platform: Platform,
 }
 
 impl ChromedriverForTestingInfo {
@@ -20,9 +39,20 @@ impl ChromedriverForTestingInfo {
         ChromedriverForTestingInfo {
             driver_install_path,
             browser_path,
+            verification_target: VerificationTarget::default(),
+            verification_config: VerificationConfig::default(),
+            verification_scenarios: default_scenarios(),
+            browser_options: BrowserOptions::default(),
+            metadata_cache: MetadataCache::default(),
+            platform: Platform::current(),
         }
     }
 
+    /// Builder with the same defaults as [`ChromedriverForTestingInfo::new`], using [`os_specific`] paths unless set.
+    pub fn builder() -> DriverInfoBuilder<Self> {
+        DriverInfoBuilder::new()
+    }
+
     /// Initialize ChromedriverForTestingInfo with default paths.
     ///
     /// # Errors
@@ -37,22 +67,4 @@ impl ChromedriverForTestingInfo {
             browser_path,
         ))
     }
-
-    /// Verify that the driver and browser versions match, without making any network requests.
-    pub fn verify_driver_offline(&self) -> Result<(), OfflineVerificationError> {
-        let driver_version = os_specific::chromedriver::binary_version(&self.driver_install_path)
-            .map_err(OfflineVerificationError::DriverVersion)?;
-        let binary_version = self
-            .binary_version()
-            .map_err(OfflineVerificationError::BinaryVersion)?;
-
-        if driver_version.major != binary_version.major {
-            Err(OfflineVerificationError::VersionMismatch {
-                driver: driver_version,
-                browser: binary_version,
-            })
-        } else {
-            Ok(())
-        }
-    }
 }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use fantoccini::wd::Capabilities;
@@ -9,13 +10,18 @@ use semver::VersionReq;
 use serde::Deserialize;
 use serde_json::{json, Map};
 
+use crate::driver_impls::builder::{BuildDriverInfo, DriverInfoSettings};
+use crate::driver_process::reported_version;
 use crate::os_specific;
+use crate::os_specific::DefaultPathError;
 use crate::prelude::{
-    UrlError, VersionReqError, VersionReqUrlInfo, WebdriverInstallationInfo,
+    OfflineVerificationInfo, Platform, UrlError, VerificationConfig, VerificationScenario,
+    VerificationTarget, VersionReqError, VersionReqUrlInfo, WebdriverInstallationInfo,
     WebdriverVerificationInfo, WebdriverVersionUrl,
 };
+use crate::registry::RegisteredDriver;
 
-use super::ChromedriverForTestingInfo;
+use super::{ChromedriverForTestingInfo, VERSIONS_SNAPSHOT, VERSIONS_URL};
 
 /// struct to parse the json from version url.
 #[derive(Debug, Deserialize)]
@@ -46,27 +52,61 @@ impl VersionReqUrlInfo for ChromedriverForTestingInfo {
     }
 
     async fn driver_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
-        let response = reqwest::get("https://googlechromelabs.github.io/chrome-for-testing/known-good-versions-with-downloads.json")
-        .await?
-        .text()
-        .await?;
+        let platform_name = self.platform_name()?;
 
-        let deserialized: JsonRoot = serde_json::from_str(&response)?;
+        self.metadata_cache
+            .get_version_urls(
+                reqwest::Client::new().get(VERSIONS_URL),
+                platform_name,
+                |json| parse_known_good_versions(json, platform_name),
+            )
+            .await
+    }
+
+    fn snapshot_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+        parse_known_good_versions(VERSIONS_SNAPSHOT, self.platform_name()?)
+    }
 
-        let versions = deserialized.versions;
+    fn cached_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+        match self.metadata_cache.cached_text(VERSIONS_URL) {
+            Some(json) => parse_known_good_versions(&json, self.platform_name()?),
+            None => Ok(Vec::new()),
+        }
+    }
+}
 
-        versions
-            .into_iter()
-            .map(version_into_webdriver_version_url)
-            .filter_map(|x| x.transpose())
-            .collect::<Result<Vec<_>, UrlError>>()
+impl ChromedriverForTestingInfo {
+    fn platform_name(&self) -> Result<&'static str, UrlError> {
+        os_specific::chromedriver_for_testing::platform_name(self.platform).ok_or_else(|| {
+            UrlError::UnsupportedPlatform {
+                platform: self.platform,
+                hint: os_specific::chromedriver::unsupported_platform_hint(self.platform),
+            }
+        })
     }
 }
 
+/// Parses the json from [`VERSIONS_URL`], keeping the downloads for `platform_name`.
+fn parse_known_good_versions(
+    json: &str,
+    platform_name: &str,
+) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+    let deserialized: JsonRoot = serde_json::from_str(json)?;
+
+    let versions = deserialized.versions;
+
+    versions
+        .into_iter()
+        .map(|version| version_into_webdriver_version_url(version, platform_name))
+        .filter_map(|x| x.transpose())
+        .collect::<Result<Vec<_>, UrlError>>()
+}
+
 /// Converts a `Version` struct into a `WebdriverVersionUrl` struct.
 /// Since there are cases where chromedriver url does not exist, this function returns `Ok(None)` if the url does not exist.
 fn version_into_webdriver_version_url(
     mut version: Version,
+    platform_name: &str,
 ) -> Result<Option<WebdriverVersionUrl>, UrlError> {
     let version_str = version.version;
     let webdriver_version = lenient_semver::parse(&version_str)
@@ -79,7 +119,7 @@ fn version_into_webdriver_version_url(
 
     let url = downloads
         .into_iter()
-        .filter(|download| download.platform == os_specific::chromedriver_for_testing::PLATFORM)
+        .filter(|download| download.platform == platform_name)
         .map(|download| download.url)
         .next();
 
@@ -91,6 +131,7 @@ fn version_into_webdriver_version_url(
         version_req,
         webdriver_version,
         url,
+        stale: false,
     }))
 }
 
@@ -100,30 +141,90 @@ impl WebdriverInstallationInfo for ChromedriverForTestingInfo {
     }
 
     fn driver_executable_name(&self) -> &'static str {
-        os_specific::chromedriver::DRIVER_EXECUTABLE_NAME
+        os_specific::chromedriver::driver_executable_name(self.platform.os)
+    }
+
+    fn target_platform(&self) -> Platform {
+        self.platform
     }
 }
 
 impl WebdriverVerificationInfo for ChromedriverForTestingInfo {
     fn driver_capabilities(&self) -> Option<Capabilities> {
+        let mut args = Vec::new();
         #[cfg(target_family = "unix")]
-        let args = match Uid::effective().is_root() {
-            true => vec!["--no-sandbox", "-headless"],
-            false => vec!["-headless"],
-        };
-        #[cfg(not(target_family = "unix"))]
-        let args = vec!["-headless"];
+        if Uid::effective().is_root() {
+            args.push("--no-sandbox");
+        }
+        if self.browser_options.headless {
+            args.push("-headless");
+        }
+
+        let mut chrome_options = Map::new();
+        chrome_options.insert("binary".to_string(), json!(self.browser_path));
+        chrome_options.insert("args".to_string(), json!(args));
+
+        Some(
+            self.browser_options
+                .to_capabilities("goog:chromeOptions", chrome_options),
+        )
+    }
+
+    fn browser_path(&self) -> Option<&Path> {
+        Some(&self.browser_path)
+    }
+
+    fn verification_target(&self) -> VerificationTarget {
+        self.verification_target.clone()
+    }
+
+    fn verification_config(&self) -> VerificationConfig {
+        self.verification_config
+    }
 
-        let capabilities_value = json!({
-            "binary": self.browser_path,
-            "args": args,
-        });
+    fn verification_scenarios(&self) -> Vec<Arc<dyn VerificationScenario>> {
+        self.verification_scenarios.clone()
+    }
 
-        let mut capabilities = Map::new();
+    fn driver_log_file_flag(&self) -> Option<&str> {
+        Some(os_specific::chromedriver::LOG_FILE_FLAG)
+    }
+}
 
-        capabilities.insert("goog:chromeOptions".to_string(), capabilities_value);
+impl OfflineVerificationInfo for ChromedriverForTestingInfo {
+    fn driver_version(&self) -> Result<semver::Version, VersionReqError> {
+        reported_version(&self.driver_install_path)
+    }
+}
+
+impl BuildDriverInfo for ChromedriverForTestingInfo {
+    type Extra = ();
+
+    fn default_driver_path(platform: Platform) -> Result<PathBuf, DefaultPathError> {
+        os_specific::chromedriver::default_driver_path_for(platform.os)
+    }
+
+    fn default_browser_path() -> Result<PathBuf, DefaultPathError> {
+        os_specific::chromedriver_for_testing::default_browser_path()
+    }
 
-        Some(capabilities)
+    fn from_settings(settings: DriverInfoSettings, _extra: ()) -> Self {
+        ChromedriverForTestingInfo {
+            driver_install_path: settings.driver_install_path,
+            browser_path: settings.browser_path,
+            verification_target: settings.verification_target,
+            verification_config: settings.verification_config,
+            verification_scenarios: settings.verification_scenarios,
+            browser_options: settings.browser_options,
+            metadata_cache: settings.metadata_cache,
+            platform: settings.platform,
+        }
+    }
+}
+
+impl RegisteredDriver for ChromedriverForTestingInfo {
+    fn as_offline(&self) -> Option<&dyn OfflineVerificationInfo> {
+        Some(self)
     }
 }
 
@@ -144,10 +245,27 @@ mod tests {
         let chromedriver_info = ChromedriverForTestingInfo {
             driver_install_path: "".into(),
             browser_path,
+            verification_target: Default::default(),
+            verification_config: Default::default(),
+            verification_scenarios: Default::default(),
+            browser_options: Default::default(),
+            metadata_cache: MetadataCache::disabled(),
+            platform: Platform::current(),
         };
 
         chromedriver_info.binary_version()?;
 
         Ok(())
     }
+
+    #[test]
+    fn test_snapshot_version_urls() -> Result<()> {
+        let info = ChromedriverForTestingInfo::new("".into(), "".into());
+
+        let version_urls = info.snapshot_version_urls()?;
+
+        assert!(!version_urls.is_empty());
+
+        Ok(())
+    }
 }
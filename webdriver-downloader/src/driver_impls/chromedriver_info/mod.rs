@@ -1,28 +1,20 @@
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use semver::Version;
-
+use crate::driver_impls::builder::DriverInfoBuilder;
 use crate::driver_impls::chromedriver_for_testing_info::ChromedriverForTestingInfo;
 use crate::driver_impls::chromedriver_old_info::ChromedriverOldInfo;
+use crate::metadata_cache::MetadataCache;
 use crate::os_specific;
 use crate::os_specific::DefaultPathError;
-use crate::traits::installation_info::WebdriverInstallationInfo;
+use crate::platform::Platform;
+use crate::traits::verification_info::{BrowserOptions, VerificationConfig, VerificationTarget};
+use crate::traits::verification_scenario::VerificationScenario;
 use crate::traits::version_req_url_info::VersionReqError;
-use crate::traits::version_req_url_info::VersionReqUrlInfo;
 
-mod trait_impls;
+pub use crate::traits::offline_verification_info::OfflineVerificationError;
 
-#[derive(thiserror::Error, Debug)]
-pub enum OfflineVerificationError {
-    #[error("Failed to get driver version")]
-    DriverVersion(VersionReqError),
-    #[error("Failed to get binary version")]
-    BinaryVersion(VersionReqError),
-    #[error("Driver and browser versions have different major version({driver} != {browser})")]
-    VersionMismatch { driver: Version, browser: Version },
-    #[error(transparent)]
-    Other(#[from] anyhow::Error),
-}
+mod trait_impls;
 
 /// Information required to implement [WebdriverDownloadInfo](crate::prelude::WebdriverDownloadInfo) for Chromedriver.
 /// This works on both old and new Chromedriver.
@@ -53,6 +45,11 @@ impl ChromedriverInfo {
         }
     }
 
+    /// Builder with the same defaults as [`ChromedriverInfo::new`], using [`os_specific`] paths unless set.
+    pub fn builder() -> DriverInfoBuilder<Self> {
+        DriverInfoBuilder::new()
+    }
+
     /// Initialize ChromedriverInfo with default paths.
     /// Searches for the new Chromedriver(Chrome for Testing) first, then the old Chromedriver.
     ///
@@ -88,26 +85,51 @@ impl ChromedriverInfo {
         }
     }
 
-    /// Verify that the driver and browser versions match, without making any network requests.
-    pub fn verify_driver_offline(&self) -> Result<(), OfflineVerificationError> {
-        let driver_install_path = match self {
-            ChromedriverInfo::OldInfo(old_info) => old_info.driver_install_path(),
-            ChromedriverInfo::NewInfo(new_info) => new_info.driver_install_path(),
-        };
-
-        let driver_version = os_specific::chromedriver::binary_version(driver_install_path)
-            .map_err(OfflineVerificationError::DriverVersion)?;
-        let binary_version = self
-            .binary_version()
-            .map_err(OfflineVerificationError::BinaryVersion)?;
-
-        if driver_version.major != binary_version.major {
-            Err(OfflineVerificationError::VersionMismatch {
-                driver: driver_version,
-                browser: binary_version,
-            })
-        } else {
-            Ok(())
+    /// Set the page to verify the driver with.
+    pub fn set_verification_target(&mut self, target: VerificationTarget) {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.verification_target = target,
+            ChromedriverInfo::NewInfo(new_info) => new_info.verification_target = target,
+        }
+    }
+
+    /// Set the timeouts to verify the driver with.
+    pub fn set_verification_config(&mut self, config: VerificationConfig) {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.verification_config = config,
+            ChromedriverInfo::NewInfo(new_info) => new_info.verification_config = config,
+        }
+    }
+
+    /// Set the browser arguments, preferences and extra capabilities to verify the driver with.
+    pub fn set_browser_options(&mut self, options: BrowserOptions) {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.browser_options = options,
+            ChromedriverInfo::NewInfo(new_info) => new_info.browser_options = options,
+        }
+    }
+
+    /// Set the cache for downloaded version metadata.
+    pub fn set_metadata_cache(&mut self, cache: MetadataCache) {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.metadata_cache = cache,
+            ChromedriverInfo::NewInfo(new_info) => new_info.metadata_cache = cache,
+        }
+    }
+
+    /// Set the platform to download the driver for.
+    pub fn set_platform(&mut self, platform: Platform) {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.platform = platform,
+            ChromedriverInfo::NewInfo(new_info) => new_info.platform = platform,
+        }
+    }
+
+    /// Set the checks to verify the driver with.
+    pub fn set_verification_scenarios(&mut self, scenarios: Vec<Arc<dyn VerificationScenario>>) {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.verification_scenarios = scenarios,
+            ChromedriverInfo::NewInfo(new_info) => new_info.verification_scenarios = scenarios,
         }
     }
 }
@@ -1,13 +1,22 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use fantoccini::wd::Capabilities;
 use semver::Version;
 
+use crate::driver_impls::builder::{BuildDriverInfo, DriverInfoSettings};
 use crate::os_specific;
+use crate::os_specific::DefaultPathError;
+use crate::platform::Platform;
+use crate::registry::RegisteredDriver;
 use crate::traits::installation_info::WebdriverInstallationInfo;
+use crate::traits::offline_verification_info::OfflineVerificationInfo;
 use crate::traits::url_info::{UrlError, WebdriverVersionUrl};
-use crate::traits::verification_info::WebdriverVerificationInfo;
+use crate::traits::verification_info::{
+    VerificationConfig, VerificationTarget, WebdriverVerificationInfo,
+};
+use crate::traits::verification_scenario::VerificationScenario;
 use crate::traits::version_req_url_info::{VersionReqError, VersionReqUrlInfo};
 
 use super::ChromedriverInfo;
@@ -29,6 +38,20 @@ impl VersionReqUrlInfo for ChromedriverInfo {
             ChromedriverInfo::NewInfo(new_info) => new_info.driver_version_urls().await,
         }
     }
+
+    fn snapshot_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.snapshot_version_urls(),
+            ChromedriverInfo::NewInfo(new_info) => new_info.snapshot_version_urls(),
+        }
+    }
+
+    fn cached_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.cached_version_urls(),
+            ChromedriverInfo::NewInfo(new_info) => new_info.cached_version_urls(),
+        }
+    }
 }
 
 impl WebdriverInstallationInfo for ChromedriverInfo {
@@ -39,8 +62,18 @@ impl WebdriverInstallationInfo for ChromedriverInfo {
         }
     }
 
-    fn driver_executable_name(&self) -> &'static str {
-        os_specific::chromedriver::DRIVER_EXECUTABLE_NAME
+    fn driver_executable_name(&self) -> &str {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.driver_executable_name(),
+            ChromedriverInfo::NewInfo(new_info) => new_info.driver_executable_name(),
+        }
+    }
+
+    fn target_platform(&self) -> Platform {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.target_platform(),
+            ChromedriverInfo::NewInfo(new_info) => new_info.target_platform(),
+        }
     }
 }
 
@@ -51,4 +84,75 @@ impl WebdriverVerificationInfo for ChromedriverInfo {
             ChromedriverInfo::NewInfo(new_info) => new_info.driver_capabilities(),
         }
     }
+
+    fn browser_path(&self) -> Option<&Path> {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.browser_path(),
+            ChromedriverInfo::NewInfo(new_info) => new_info.browser_path(),
+        }
+    }
+
+    fn verification_target(&self) -> VerificationTarget {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.verification_target(),
+            ChromedriverInfo::NewInfo(new_info) => new_info.verification_target(),
+        }
+    }
+
+    fn verification_config(&self) -> VerificationConfig {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.verification_config(),
+            ChromedriverInfo::NewInfo(new_info) => new_info.verification_config(),
+        }
+    }
+
+    fn verification_scenarios(&self) -> Vec<Arc<dyn VerificationScenario>> {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.verification_scenarios(),
+            ChromedriverInfo::NewInfo(new_info) => new_info.verification_scenarios(),
+        }
+    }
+
+    fn driver_log_file_flag(&self) -> Option<&str> {
+        Some(os_specific::chromedriver::LOG_FILE_FLAG)
+    }
+}
+
+impl OfflineVerificationInfo for ChromedriverInfo {
+    fn driver_version(&self) -> Result<Version, VersionReqError> {
+        match self {
+            ChromedriverInfo::OldInfo(old_info) => old_info.driver_version(),
+            ChromedriverInfo::NewInfo(new_info) => new_info.driver_version(),
+        }
+    }
+}
+
+impl BuildDriverInfo for ChromedriverInfo {
+    type Extra = ();
+
+    fn default_driver_path(platform: Platform) -> Result<PathBuf, DefaultPathError> {
+        os_specific::chromedriver::default_driver_path_for(platform.os)
+    }
+
+    fn default_browser_path() -> Result<PathBuf, DefaultPathError> {
+        os_specific::chromedriver::default_browser_path()
+    }
+
+    fn from_settings(settings: DriverInfoSettings, _extra: ()) -> Self {
+        let mut info = ChromedriverInfo::new(settings.driver_install_path, settings.browser_path);
+        info.set_verification_target(settings.verification_target);
+        info.set_verification_config(settings.verification_config);
+        info.set_verification_scenarios(settings.verification_scenarios);
+        info.set_browser_options(settings.browser_options);
+        info.set_metadata_cache(settings.metadata_cache);
+        info.set_platform(settings.platform);
+
+        info
+    }
+}
+
+impl RegisteredDriver for ChromedriverInfo {
+    fn as_offline(&self) -> Option<&dyn OfflineVerificationInfo> {
+        Some(self)
+    }
 }
@@ -1,17 +1,35 @@
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use crate::driver_impls::chromedriver_info::OfflineVerificationError;
+use crate::driver_impls::builder::DriverInfoBuilder;
+use crate::metadata_cache::MetadataCache;
 use crate::os_specific;
 use crate::os_specific::DefaultPathError;
-use crate::traits::version_req_url_info::VersionReqUrlInfo;
+use crate::platform::Platform;
+use crate::traits::verification_info::{BrowserOptions, VerificationConfig, VerificationTarget};
+use crate::traits::verification_scenario::{default_scenarios, VerificationScenario};
 
 mod trait_impls;
 
+/// Listing of the chromedriver storage bucket, which has the download of every old chromedriver version.
+pub const VERSIONS_URL: &str = "https://chromedriver.storage.googleapis.com";
+
+/// Snapshot of [`VERSIONS_URL`], trimmed to the newest version of each major version. Used if it is unreachable.
+/// Regenerate with `cargo run --example update_snapshots`.
+const VERSIONS_SNAPSHOT: &str = include_str!("snapshot.xml");
+
 /// Information required to implement [WebdriverDownloadInfo](crate::prelude::WebdriverDownloadInfo) for old Chromedriver.
 #[derive(Debug)]
 pub struct ChromedriverOldInfo {
     pub driver_install_path: PathBuf,
     pub browser_path: PathBuf,
+    pub verification_target: VerificationTarget,
+    pub verification_config: VerificationConfig,
+    pub verification_scenarios: Vec<Arc<dyn VerificationScenario>>,
+    pub browser_options: BrowserOptions,
+    /// Cache for the version metadata downloaded by [`driver_version_urls`](crate::prelude::VersionReqUrlInfo::driver_version_urls).
+    pub metadata_cache: MetadataCache,
+    pub platform: Platform,
 }
 
 impl ChromedriverOldInfo {
@@ -19,9 +37,20 @@ impl ChromedriverOldInfo {
         ChromedriverOldInfo {
             driver_install_path,
             browser_path,
+            verification_target: VerificationTarget::default(),
+            verification_config: VerificationConfig::default(),
+            verification_scenarios: default_scenarios(),
+            browser_options: BrowserOptions::default(),
+            metadata_cache: MetadataCache::default(),
+            platform: Platform::current(),
         }
     }
 
+    /// Builder with the same defaults as [`ChromedriverOldInfo::new`], using [`os_specific`] paths unless set.
+    pub fn builder() -> DriverInfoBuilder<Self> {
+        DriverInfoBuilder::new()
+    }
+
     /// Initialize ChromedriverOldInfo with default paths.
     ///
     /// # Errors
@@ -32,22 +61,4 @@ impl ChromedriverOldInfo {
         let browser_path = os_specific::chromedriver_old::default_browser_path()?;
         Ok(ChromedriverOldInfo::new(driver_install_path, browser_path))
     }
-
-    /// Verify that the driver and browser versions match, without making any network requests.
-    pub fn verify_driver_offline(&self) -> Result<(), OfflineVerificationError> {
-        let driver_version = os_specific::chromedriver::binary_version(&self.driver_install_path)
-            .map_err(OfflineVerificationError::DriverVersion)?;
-        let binary_version = self
-            .binary_version()
-            .map_err(OfflineVerificationError::BinaryVersion)?;
-
-        if driver_version.major != binary_version.major {
-            Err(OfflineVerificationError::VersionMismatch {
-                driver: driver_version,
-                browser: binary_version,
-            })
-        } else {
-            Ok(())
-        }
-    }
 }
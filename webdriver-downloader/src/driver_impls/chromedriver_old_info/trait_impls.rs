@@ -1,4 +1,5 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use fantoccini::wd::Capabilities;
@@ -8,13 +9,22 @@ use regex::Regex;
 use semver::{Version, VersionReq};
 use serde_json::{json, Map};
 
+use crate::driver_impls::builder::{BuildDriverInfo, DriverInfoSettings};
+use crate::driver_process::reported_version;
 use crate::os_specific;
+use crate::os_specific::DefaultPathError;
+use crate::platform::Platform;
+use crate::registry::RegisteredDriver;
 use crate::traits::installation_info::WebdriverInstallationInfo;
+use crate::traits::offline_verification_info::OfflineVerificationInfo;
 use crate::traits::url_info::{UrlError, WebdriverVersionUrl};
-use crate::traits::verification_info::WebdriverVerificationInfo;
+use crate::traits::verification_info::{
+    VerificationConfig, VerificationTarget, WebdriverVerificationInfo,
+};
+use crate::traits::verification_scenario::VerificationScenario;
 use crate::traits::version_req_url_info::{VersionReqError, VersionReqUrlInfo};
 
-use super::ChromedriverOldInfo;
+use super::{ChromedriverOldInfo, VERSIONS_SNAPSHOT, VERSIONS_URL};
 
 #[async_trait]
 impl VersionReqUrlInfo for ChromedriverOldInfo {
@@ -23,34 +33,70 @@ impl VersionReqUrlInfo for ChromedriverOldInfo {
     }
 
     async fn driver_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
-        let download_xml = "https://chromedriver.storage.googleapis.com";
-
-        let xml = reqwest::get(download_xml).await?.text().await?;
-
-        let re = Regex::new(os_specific::chromedriver_old::ZIPFILE_NAME_RE)
-            .expect("Failed to parse regex.");
-
-        let mut versions: Vec<WebdriverVersionUrl> = vec![];
-        for captures in re.captures_iter(&xml) {
-            let or_else =
-                || VersionReqError::RegexError(captures.get(0).unwrap().as_str().to_string());
-
-            let version_str = captures.get(1).ok_or_else(or_else)?.as_str();
-            let webdriver_version = lenient_semver::parse(version_str)
-                .map_err(|e| VersionReqError::ParseVersion(e.owned()))?;
+        let platform_name = self.platform_name()?;
+
+        self.metadata_cache
+            .get_version_urls(
+                reqwest::Client::new().get(VERSIONS_URL),
+                platform_name,
+                |xml| parse_bucket_listing(xml, platform_name),
+            )
+            .await
+    }
 
-            let version_req = VersionReq::parse(&format!("^{}", webdriver_version))
-                .map_err(VersionReqError::ParseVersionReq)?;
+    fn snapshot_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+        parse_bucket_listing(VERSIONS_SNAPSHOT, self.platform_name()?)
+    }
 
-            versions.push(WebdriverVersionUrl {
-                version_req,
-                webdriver_version,
-                url: os_specific::chromedriver_old::build_url(version_str),
-            });
+    fn cached_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+        match self.metadata_cache.cached_text(VERSIONS_URL) {
+            Some(xml) => parse_bucket_listing(&xml, self.platform_name()?),
+            None => Ok(Vec::new()),
         }
+    }
+}
+
+impl ChromedriverOldInfo {
+    fn platform_name(&self) -> Result<&'static str, UrlError> {
+        os_specific::chromedriver_old::platform_name(self.platform).ok_or_else(|| {
+            UrlError::UnsupportedPlatform {
+                platform: self.platform,
+                hint: os_specific::chromedriver::unsupported_platform_hint(self.platform),
+            }
+        })
+    }
+}
 
-        Ok(versions)
+/// Parses the xml listing of the chromedriver storage bucket, keeping the zip files for `platform_name`.
+fn parse_bucket_listing(
+    xml: &str,
+    platform_name: &str,
+) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+    let re = Regex::new(&os_specific::chromedriver_old::zipfile_name_re(
+        platform_name,
+    ))
+    .expect("Failed to parse regex.");
+
+    let mut versions: Vec<WebdriverVersionUrl> = vec![];
+    for captures in re.captures_iter(xml) {
+        let or_else = || VersionReqError::RegexError(captures.get(0).unwrap().as_str().to_string());
+
+        let version_str = captures.get(1).ok_or_else(or_else)?.as_str();
+        let webdriver_version = lenient_semver::parse(version_str)
+            .map_err(|e| VersionReqError::ParseVersion(e.owned()))?;
+
+        let version_req = VersionReq::parse(&format!("^{}", webdriver_version))
+            .map_err(VersionReqError::ParseVersionReq)?;
+
+        versions.push(WebdriverVersionUrl {
+            version_req,
+            webdriver_version,
+            url: os_specific::chromedriver_old::build_url(version_str, platform_name),
+            stale: false,
+        });
     }
+
+    Ok(versions)
 }
 
 impl WebdriverInstallationInfo for ChromedriverOldInfo {
@@ -59,30 +105,90 @@ impl WebdriverInstallationInfo for ChromedriverOldInfo {
     }
 
     fn driver_executable_name(&self) -> &'static str {
-        os_specific::chromedriver::DRIVER_EXECUTABLE_NAME
+        os_specific::chromedriver::driver_executable_name(self.platform.os)
+    }
+
+    fn target_platform(&self) -> Platform {
+        self.platform
     }
 }
 
 impl WebdriverVerificationInfo for ChromedriverOldInfo {
     fn driver_capabilities(&self) -> Option<Capabilities> {
+        let mut args = Vec::new();
         #[cfg(target_family = "unix")]
-        let args = match Uid::effective().is_root() {
-            true => vec!["--no-sandbox", "-headless"],
-            false => vec!["-headless"],
-        };
-        #[cfg(not(target_family = "unix"))]
-        let args = vec!["-headless"];
+        if Uid::effective().is_root() {
+            args.push("--no-sandbox");
+        }
+        if self.browser_options.headless {
+            args.push("-headless");
+        }
 
-        let capabilities_value = json!({
-            "binary": self.browser_path,
-            "args": args,
-        });
+        let mut chrome_options = Map::new();
+        chrome_options.insert("binary".to_string(), json!(self.browser_path));
+        chrome_options.insert("args".to_string(), json!(args));
 
-        let mut capabilities = Map::new();
+        Some(
+            self.browser_options
+                .to_capabilities("goog:chromeOptions", chrome_options),
+        )
+    }
+
+    fn browser_path(&self) -> Option<&Path> {
+        Some(&self.browser_path)
+    }
 
-        capabilities.insert("goog:chromeOptions".to_string(), capabilities_value);
+    fn verification_target(&self) -> VerificationTarget {
+        self.verification_target.clone()
+    }
+
+    fn verification_config(&self) -> VerificationConfig {
+        self.verification_config
+    }
 
-        Some(capabilities)
+    fn verification_scenarios(&self) -> Vec<Arc<dyn VerificationScenario>> {
+        self.verification_scenarios.clone()
+    }
+
+    fn driver_log_file_flag(&self) -> Option<&str> {
+        Some(os_specific::chromedriver::LOG_FILE_FLAG)
+    }
+}
+
+impl OfflineVerificationInfo for ChromedriverOldInfo {
+    fn driver_version(&self) -> Result<Version, VersionReqError> {
+        reported_version(&self.driver_install_path)
+    }
+}
+
+impl BuildDriverInfo for ChromedriverOldInfo {
+    type Extra = ();
+
+    fn default_driver_path(platform: Platform) -> Result<PathBuf, DefaultPathError> {
+        os_specific::chromedriver::default_driver_path_for(platform.os)
+    }
+
+    fn default_browser_path() -> Result<PathBuf, DefaultPathError> {
+        os_specific::chromedriver_old::default_browser_path()
+    }
+
+    fn from_settings(settings: DriverInfoSettings, _extra: ()) -> Self {
+        ChromedriverOldInfo {
+            driver_install_path: settings.driver_install_path,
+            browser_path: settings.browser_path,
+            verification_target: settings.verification_target,
+            verification_config: settings.verification_config,
+            verification_scenarios: settings.verification_scenarios,
+            browser_options: settings.browser_options,
+            metadata_cache: settings.metadata_cache,
+            platform: settings.platform,
+        }
+    }
+}
+
+impl RegisteredDriver for ChromedriverOldInfo {
+    fn as_offline(&self) -> Option<&dyn OfflineVerificationInfo> {
+        Some(self)
     }
 }
 
@@ -103,10 +209,27 @@ mod tests {
         let chromedriver_info = ChromedriverOldInfo {
             driver_install_path: "".into(),
             browser_path,
+            verification_target: Default::default(),
+            verification_config: Default::default(),
+            verification_scenarios: Default::default(),
+            browser_options: Default::default(),
+            metadata_cache: MetadataCache::disabled(),
+            platform: Platform::current(),
         };
 
         chromedriver_info.binary_version()?;
 
         Ok(())
     }
+
+    #[test]
+    fn test_snapshot_version_urls() -> Result<()> {
+        let info = ChromedriverOldInfo::new("".into(), "".into());
+
+        let version_urls = info.snapshot_version_urls()?;
+
+        assert!(!version_urls.is_empty());
+
+        Ok(())
+    }
 }
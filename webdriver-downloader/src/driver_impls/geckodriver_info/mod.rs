@@ -1,29 +1,158 @@
+use std::fmt::{Debug, Formatter};
 use std::path::PathBuf;
+use std::sync::Arc;
 
+use semver::{Version, VersionReq};
+
+use crate::driver_impls::builder::DriverInfoBuilder;
+use crate::metadata_cache::MetadataCache;
 use crate::os_specific;
 use crate::os_specific::DefaultPathError;
+use crate::platform::Platform;
+use crate::traits::offline_verification_info::OfflineVerificationError;
+use crate::traits::url_info::WebdriverVersionUrl;
+use crate::traits::verification_info::{BrowserOptions, VerificationConfig, VerificationTarget};
+use crate::traits::verification_scenario::{default_scenarios, VerificationScenario};
 
 mod trait_impls;
 
+/// Geckodriver releases on GitHub, with their assets.
+pub const RELEASES_URL: &str =
+    "https://api.github.com/repos/mozilla/geckodriver/releases?per_page=100";
+
+/// Snapshot of [`RELEASES_URL`], trimmed to the newest stable releases. Used if it is unreachable.
+/// Regenerate with `cargo run --example update_snapshots`.
+const RELEASES_SNAPSHOT: &str = include_str!("snapshot.json");
+
+/// geckodriver's `Support.md`, with the table of Firefox versions each release supports.
+pub const SUPPORT_TABLE_URL: &str =
+    "https://firefox-source-docs.mozilla.org/_sources/testing/geckodriver/Support.md.txt";
+
+/// Snapshot of the table in [`SUPPORT_TABLE_URL`], used for Firefox compatibility ranges
+/// if it was never downloaded into the [`MetadataCache`].
+/// Regenerate with `cargo run --example update_snapshots` when geckodriver releases.
+const BUNDLED_SUPPORT_TABLE: &str = include_str!("support_table.md");
+
+/// Environment variable [`GeckodriverInfo::new`] reads the GitHub api token from.
+pub const GITHUB_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+/// Token for the GitHub api, which raises its rate limit. Redacted from [`Debug`] output.
+#[derive(Clone)]
+pub struct GithubToken(pub String);
+
+impl Debug for GithubToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("GithubToken(..)")
+    }
+}
+
+/// Geckodriver specific settings of [`GeckodriverInfo::builder`].
+#[derive(Debug, Default)]
+pub struct GeckodriverExtra {
+    pub github_token: Option<GithubToken>,
+}
+
+impl DriverInfoBuilder<GeckodriverInfo> {
+    /// Token for the GitHub api. Defaults to the [`GITHUB_TOKEN_ENV`] environment variable.
+    pub fn github_token(mut self, token: impl Into<String>) -> Self {
+        self.extra.github_token = Some(GithubToken(token.into()));
+        self
+    }
+}
+
 /// Information required to implement [WebdriverDownloadInfo](crate::prelude::WebdriverDownloadInfo) for Geckodriver.
+/// Versions are resolved from geckodriver's GitHub releases.
 #[derive(Debug)]
 pub struct GeckodriverInfo {
     pub driver_install_path: PathBuf,
     pub browser_path: PathBuf,
+    pub verification_target: VerificationTarget,
+    pub verification_config: VerificationConfig,
+    pub verification_scenarios: Vec<Arc<dyn VerificationScenario>>,
+    pub browser_options: BrowserOptions,
+    /// Cache for the version metadata downloaded by [`driver_version_urls`](crate::prelude::VersionReqUrlInfo::driver_version_urls).
+    pub metadata_cache: MetadataCache,
+    pub platform: Platform,
+    pub github_token: Option<GithubToken>,
 }
 
 impl GeckodriverInfo {
+    /// Reads [`Self::github_token`] from the [`GITHUB_TOKEN_ENV`] environment variable, if set.
     #[tracing::instrument]
     pub fn new(driver_install_path: PathBuf, browser_path: PathBuf) -> Self {
         GeckodriverInfo {
             driver_install_path,
             browser_path,
+            verification_target: VerificationTarget::default(),
+            verification_config: VerificationConfig::default(),
+            verification_scenarios: default_scenarios(),
+            browser_options: BrowserOptions::default(),
+            metadata_cache: MetadataCache::default(),
+            platform: Platform::current(),
+            github_token: std::env::var(GITHUB_TOKEN_ENV).ok().map(GithubToken),
         }
     }
 
+    /// Builder with the same defaults as [`GeckodriverInfo::new`], using [`os_specific`] paths unless set.
+    pub fn builder() -> DriverInfoBuilder<Self> {
+        DriverInfoBuilder::new()
+    }
+
     pub fn new_default() -> Result<Self, DefaultPathError> {
         let driver_install_path = os_specific::geckodriver::default_driver_path()?;
         let browser_path = os_specific::geckodriver::default_browser_path()?;
         Ok(GeckodriverInfo::new(driver_install_path, browser_path))
     }
 }
+
+/// Looks up the browser versions `driver` supports in the support table.
+/// Drivers missing from the table use the closest older entry, since patch releases are not always listed.
+fn supported_browser_versions(
+    support_table: &[WebdriverVersionUrl],
+    driver: &Version,
+) -> Result<VersionReq, OfflineVerificationError> {
+    support_table
+        .iter()
+        .filter(|entry| entry.webdriver_version <= *driver)
+        .max_by(|a, b| a.webdriver_version.cmp(&b.webdriver_version))
+        .map(|entry| entry.version_req.clone())
+        .ok_or_else(|| OfflineVerificationError::UnknownDriverVersion(driver.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+
+    use crate::traits::offline_verification_info::OfflineVerificationError;
+
+    use super::{supported_browser_versions, trait_impls, BUNDLED_SUPPORT_TABLE};
+
+    #[test]
+    fn bundled_support_table_parses() {
+        let support_table = trait_impls::parse_support_table(BUNDLED_SUPPORT_TABLE).unwrap();
+
+        assert!(support_table
+            .iter()
+            .any(|entry| entry.webdriver_version == Version::new(0, 33, 0)));
+    }
+
+    #[test]
+    fn supported_browser_versions_from_table() {
+        let support_table = trait_impls::parse_support_table(BUNDLED_SUPPORT_TABLE).unwrap();
+
+        let supported =
+            supported_browser_versions(&support_table, &Version::new(0, 33, 0)).unwrap();
+        assert!(supported.matches(&Version::new(115, 0, 0)));
+        assert!(!supported.matches(&Version::new(121, 0, 0)));
+        assert!(!supported.matches(&Version::new(91, 0, 0)));
+
+        // Newer drivers than the table use its newest entry, which has no maximum.
+        let supported = supported_browser_versions(&support_table, &Version::new(9, 0, 0)).unwrap();
+        assert!(supported.matches(&Version::new(200, 0, 0)));
+
+        assert!(matches!(
+            supported_browser_versions(&support_table, &Version::new(0, 1, 0)),
+            Err(OfflineVerificationError::UnknownDriverVersion(_))
+        ));
+    }
+}
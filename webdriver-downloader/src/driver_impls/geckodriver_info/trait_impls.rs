@@ -1,18 +1,48 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use fantoccini::wd::Capabilities;
 use regex::Regex;
+use reqwest::header;
 use semver::{Version, VersionReq};
+use serde::Deserialize;
 use serde_json::{json, Map};
 
+use crate::driver_impls::builder::{BuildDriverInfo, DriverInfoSettings};
+use crate::driver_process::reported_version;
 use crate::os_specific;
+use crate::os_specific::DefaultPathError;
+use crate::platform::Platform;
+use crate::registry::RegisteredDriver;
 use crate::traits::installation_info::WebdriverInstallationInfo;
+use crate::traits::offline_verification_info::{OfflineVerificationError, OfflineVerificationInfo};
 use crate::traits::url_info::{UrlError, WebdriverVersionUrl};
-use crate::traits::verification_info::WebdriverVerificationInfo;
+use crate::traits::verification_info::{
+    VerificationConfig, VerificationTarget, WebdriverVerificationInfo,
+};
+use crate::traits::verification_scenario::VerificationScenario;
 use crate::traits::version_req_url_info::{VersionReqError, VersionReqUrlInfo};
 
-use super::GeckodriverInfo;
+use super::{
+    supported_browser_versions, GeckodriverExtra, GeckodriverInfo, BUNDLED_SUPPORT_TABLE,
+    RELEASES_SNAPSHOT, RELEASES_URL, SUPPORT_TABLE_URL,
+};
+
+/// struct to parse a release from the GitHub releases api.
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    draft: bool,
+    prerelease: bool,
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
 
 #[async_trait]
 impl VersionReqUrlInfo for GeckodriverInfo {
@@ -21,56 +51,188 @@ impl VersionReqUrlInfo for GeckodriverInfo {
     }
 
     async fn driver_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
-        let download_html =
-            "https://firefox-source-docs.mozilla.org/_sources/testing/geckodriver/Support.md.txt";
+        let asset_suffix = self.asset_suffix()?;
+        let support_md = match self
+            .metadata_cache
+            .get_text(reqwest::Client::new().get(SUPPORT_TABLE_URL))
+            .await
+        {
+            Ok(support_md) => Some(support_md),
+            Err(e) => {
+                tracing::warn!("Failed to download support table: {}", e);
+                self.metadata_cache.cached_text(SUPPORT_TABLE_URL)
+            }
+        };
+        let support_table = support_table_or_bundled(support_md.as_deref())?;
+        // Releases are matched against the table, so lists parsed with an older table are not reused.
+        let memo_key = match support_table
+            .iter()
+            .map(|entry| &entry.webdriver_version)
+            .max()
+        {
+            Some(newest) => format!("{} {}", asset_suffix, newest),
+            None => asset_suffix.to_string(),
+        };
 
-        let html = reqwest::get(download_html).await?.text().await?;
+        let mut request = reqwest::Client::new()
+            .get(RELEASES_URL)
+            .header(header::ACCEPT, "application/vnd.github+json")
+            .header(header::USER_AGENT, "webdriver-downloader");
+        if let Some(token) = &self.github_token {
+            request = request.bearer_auth(&token.0);
+        }
 
-        let html = html
-            .lines()
-            .skip_while(|line| *line != "<table>")
-            .take_while(|line| !line.is_empty())
-            .collect::<String>();
+        self.metadata_cache
+            .get_version_urls(request, &memo_key, |json| {
+                parse_releases(json, &support_table, asset_suffix)
+            })
+            .await
+    }
 
-        // parses <tr>  <td>0.33.0  <td>≥ 3.11 (3.14 Python)  <td>102 ESR  <td>n/a
-        // or <tr>  <td>0.19.0  <td>≥ 3.5  <td>55  <td>62
-        let re = Regex::new(
-            r"<tr>\s*<td>([0-9.]*?)\s*<td>[^<]*<td>([0-9.]*?)( ESR)?\s*<td>([0-9.]*|n/a)",
+    fn snapshot_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+        parse_releases(
+            RELEASES_SNAPSHOT,
+            &self.support_table()?,
+            self.asset_suffix()?,
         )
-        .unwrap();
+    }
+
+    fn cached_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+        match self.metadata_cache.cached_text(RELEASES_URL) {
+            Some(json) => parse_releases(&json, &self.support_table()?, self.asset_suffix()?),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl GeckodriverInfo {
+    fn asset_suffix(&self) -> Result<&'static str, UrlError> {
+        os_specific::geckodriver::asset_suffix(self.platform).ok_or(UrlError::UnsupportedPlatform {
+            platform: self.platform,
+            hint: None,
+        })
+    }
+
+    /// Support table from the [`MetadataCache`], even if expired, or the bundled copy.
+    fn support_table(&self) -> Result<Vec<WebdriverVersionUrl>, VersionReqError> {
+        support_table_or_bundled(
+            self.metadata_cache
+                .cached_text(SUPPORT_TABLE_URL)
+                .as_deref(),
+        )
+    }
+}
+
+/// Parses `support_md`, falling back to [`BUNDLED_SUPPORT_TABLE`] if it is missing, invalid or empty.
+fn support_table_or_bundled(
+    support_md: Option<&str>,
+) -> Result<Vec<WebdriverVersionUrl>, VersionReqError> {
+    if let Some(support_md) = support_md {
+        match parse_support_table(support_md) {
+            Ok(support_table) if !support_table.is_empty() => return Ok(support_table),
+            Ok(_) => tracing::warn!("Support table has no entries, using the bundled copy"),
+            Err(e) => tracing::warn!(
+                "Failed to parse support table, using the bundled copy: {}",
+                e
+            ),
+        }
+    }
 
-        let mut versions: Vec<WebdriverVersionUrl> = vec![];
-        for captures in re.captures_iter(&html) {
-            let or_else =
-                || VersionReqError::RegexError(captures.get(0).unwrap().as_str().to_string());
+    parse_support_table(BUNDLED_SUPPORT_TABLE)
+}
 
-            let version_str = captures.get(1).ok_or_else(or_else)?.as_str();
-            let min_firefox_version_str = captures.get(2).ok_or_else(or_else)?.as_str();
-            let max_firefox_version_str = captures.get(4).ok_or_else(or_else)?.as_str();
+/// Parses the json from [`RELEASES_URL`], using the assets ending with `asset_suffix`.
+fn parse_releases(
+    json: &str,
+    support_table: &[WebdriverVersionUrl],
+    asset_suffix: &str,
+) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+    let releases: Vec<Release> = serde_json::from_str(json)?;
 
-            let webdriver_version = lenient_semver::parse(version_str)
-                .map_err(|e| VersionReqError::ParseVersion(e.owned()))?;
-            let min_version = lenient_semver::parse(min_firefox_version_str)
-                .map_err(|e| VersionReqError::ParseVersion(e.owned()))?;
-            let max_version = lenient_semver::parse(max_firefox_version_str).ok();
+    Ok(release_version_urls(releases, support_table, asset_suffix))
+}
 
-            let version_req_string = match max_version {
-                Some(max_version) => format!(">= {}, <= {}", min_version, max_version),
-                None => format!(">= {}", min_version),
-            };
+/// Converts GitHub releases into [`WebdriverVersionUrl`]s, using the assets ending with `asset_suffix`
+/// and the Firefox versions from `support_table`.
+/// Drafts, prereleases, releases without a matching asset and releases older than `support_table` are skipped.
+fn release_version_urls(
+    releases: Vec<Release>,
+    support_table: &[WebdriverVersionUrl],
+    asset_suffix: &str,
+) -> Vec<WebdriverVersionUrl> {
+    releases
+        .into_iter()
+        .filter(|release| !release.draft && !release.prerelease)
+        .filter_map(|release| {
+            let webdriver_version =
+                lenient_semver::parse(release.tag_name.trim_start_matches('v')).ok()?;
+            let version_req = supported_browser_versions(support_table, &webdriver_version).ok()?;
 
-            let version_req =
-                VersionReq::parse(&version_req_string).map_err(VersionReqError::ParseVersionReq)?;
+            let asset_name = os_specific::geckodriver::asset_name(&release.tag_name, asset_suffix);
+            let url = release
+                .assets
+                .into_iter()
+                .find(|asset| asset.name == asset_name)?
+                .browser_download_url;
 
-            versions.push(WebdriverVersionUrl {
+            Some(WebdriverVersionUrl {
                 version_req,
                 webdriver_version,
-                url: os_specific::geckodriver::build_url(version_str),
+                url,
+                stale: false,
             })
-        }
+        })
+        .collect()
+}
+
+/// Parses the table of supported Firefox versions from geckodriver's `Support.md`.
+/// The entries have no download url.
+pub(super) fn parse_support_table(
+    support_md: &str,
+) -> Result<Vec<WebdriverVersionUrl>, VersionReqError> {
+    let html = support_md
+        .lines()
+        .skip_while(|line| *line != "<table>")
+        .take_while(|line| !line.is_empty())
+        .collect::<String>();
+
+    // parses <tr>  <td>0.33.0  <td>≥ 3.11 (3.14 Python)  <td>102 ESR  <td>n/a
+    // or <tr>  <td>0.19.0  <td>≥ 3.5  <td>55  <td>62
+    let re =
+        Regex::new(r"<tr>\s*<td>([0-9.]*?)\s*<td>[^<]*<td>([0-9.]*?)( ESR)?\s*<td>([0-9.]*|n/a)")
+            .unwrap();
+
+    let mut versions: Vec<WebdriverVersionUrl> = vec![];
+    for captures in re.captures_iter(&html) {
+        let or_else = || VersionReqError::RegexError(captures.get(0).unwrap().as_str().to_string());
+
+        let version_str = captures.get(1).ok_or_else(or_else)?.as_str();
+        let min_firefox_version_str = captures.get(2).ok_or_else(or_else)?.as_str();
+        let max_firefox_version_str = captures.get(4).ok_or_else(or_else)?.as_str();
+
+        let webdriver_version = lenient_semver::parse(version_str)
+            .map_err(|e| VersionReqError::ParseVersion(e.owned()))?;
+        let min_version = lenient_semver::parse(min_firefox_version_str)
+            .map_err(|e| VersionReqError::ParseVersion(e.owned()))?;
+        let max_version = lenient_semver::parse(max_firefox_version_str).ok();
+
+        let version_req_string = match max_version {
+            Some(max_version) => format!(">= {}, <= {}", min_version, max_version),
+            None => format!(">= {}", min_version),
+        };
+
+        let version_req =
+            VersionReq::parse(&version_req_string).map_err(VersionReqError::ParseVersionReq)?;
 
-        Ok(versions)
+        versions.push(WebdriverVersionUrl {
+            version_req,
+            webdriver_version,
+            url: String::new(),
+            stale: false,
+        })
     }
+
+    Ok(versions)
 }
 
 impl WebdriverInstallationInfo for GeckodriverInfo {
@@ -79,33 +241,114 @@ impl WebdriverInstallationInfo for GeckodriverInfo {
     }
 
     fn driver_executable_name(&self) -> &'static str {
-        os_specific::geckodriver::DRIVER_EXECUTABLE_NAME
+        os_specific::geckodriver::driver_executable_name(self.platform.os)
+    }
+
+    fn target_platform(&self) -> Platform {
+        self.platform
     }
 }
 
 impl WebdriverVerificationInfo for GeckodriverInfo {
     fn driver_capabilities(&self) -> Option<Capabilities> {
-        let capabilities_value = json!({
-            "binary": self.browser_path,
-            "args": ["-headless"],
-        });
+        let args = match self.browser_options.headless {
+            true => vec!["-headless"],
+            false => vec![],
+        };
 
-        let mut capabilities = Map::new();
+        let mut firefox_options = Map::new();
+        firefox_options.insert("binary".to_string(), json!(self.browser_path));
+        firefox_options.insert("args".to_string(), json!(args));
 
-        capabilities.insert("moz:firefoxOptions".to_string(), capabilities_value);
+        Some(
+            self.browser_options
+                .to_capabilities("moz:firefoxOptions", firefox_options),
+        )
+    }
 
-        Some(capabilities)
+    fn browser_path(&self) -> Option<&Path> {
+        Some(&self.browser_path)
+    }
+
+    fn verification_target(&self) -> VerificationTarget {
+        self.verification_target.clone()
+    }
+
+    fn verification_config(&self) -> VerificationConfig {
+        self.verification_config
+    }
+
+    fn verification_scenarios(&self) -> Vec<Arc<dyn VerificationScenario>> {
+        self.verification_scenarios.clone()
+    }
+}
+
+impl OfflineVerificationInfo for GeckodriverInfo {
+    fn driver_version(&self) -> Result<Version, VersionReqError> {
+        reported_version(&self.driver_install_path)
+    }
+
+    /// Looks up `driver_version` in geckodriver's support table, cached or bundled.
+    fn supported_browser_versions(
+        &self,
+        driver_version: &Version,
+    ) -> Result<VersionReq, OfflineVerificationError> {
+        let support_table = self
+            .support_table()
+            .map_err(OfflineVerificationError::SupportTable)?;
+
+        supported_browser_versions(&support_table, driver_version)
+    }
+}
+
+impl BuildDriverInfo for GeckodriverInfo {
+    type Extra = GeckodriverExtra;
+
+    fn default_driver_path(platform: Platform) -> Result<PathBuf, DefaultPathError> {
+        os_specific::geckodriver::default_driver_path_for(platform.os)
+    }
+
+    fn default_browser_path() -> Result<PathBuf, DefaultPathError> {
+        os_specific::geckodriver::default_browser_path()
+    }
+
+    /// Keeps the token [`GeckodriverInfo::new`] reads from the environment unless one was set.
+    fn from_settings(settings: DriverInfoSettings, extra: GeckodriverExtra) -> Self {
+        let mut info = GeckodriverInfo::new(settings.driver_install_path, settings.browser_path);
+        info.verification_target = settings.verification_target;
+        info.verification_config = settings.verification_config;
+        info.verification_scenarios = settings.verification_scenarios;
+        info.browser_options = settings.browser_options;
+        info.metadata_cache = settings.metadata_cache;
+        info.platform = settings.platform;
+        if let Some(github_token) = extra.github_token {
+            info.github_token = Some(github_token);
+        }
+
+        info
+    }
+}
+
+impl RegisteredDriver for GeckodriverInfo {
+    fn as_offline(&self) -> Option<&dyn OfflineVerificationInfo> {
+        Some(self)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
+    use serde_json::json;
     use test_log::test;
 
     use crate::prelude::GeckodriverInfo;
     use crate::prelude::*;
 
+    use super::{
+        parse_support_table, release_version_urls, support_table_or_bundled, Release,
+        BUNDLED_SUPPORT_TABLE,
+    };
+
     #[test]
     fn test_get_binary_version() -> Result<()> {
         let browser_path = os_specific::geckodriver::default_browser_path()
@@ -114,10 +357,96 @@ mod tests {
         let geckodriver_info = GeckodriverInfo {
             driver_install_path: "".into(),
             browser_path,
+            verification_target: Default::default(),
+            verification_config: Default::default(),
+            verification_scenarios: Default::default(),
+            browser_options: Default::default(),
+            metadata_cache: MetadataCache::disabled(),
+            platform: Platform::current(),
+            github_token: None,
         };
 
         geckodriver_info.binary_version()?;
 
         Ok(())
     }
+
+    #[test]
+    fn test_release_version_urls() -> Result<()> {
+        let tag = |version: &str| format!("v{}", version);
+        let asset = |version: &str, suffix: &str| {
+            let name = format!("geckodriver-v{}-{}", version, suffix);
+            json!({"name": name, "browser_download_url": format!("https://example.com/{}", name)})
+        };
+        let release = |version: &str, prerelease: bool| {
+            json!({
+                "tag_name": tag(version),
+                "draft": false,
+                "prerelease": prerelease,
+                "assets": [
+                    asset(version, "linux64.tar.gz"),
+                    asset(version, "linux64.tar.gz.asc"),
+                    asset(version, "win64.zip"),
+                ],
+            })
+        };
+        let releases: Vec<Release> = serde_json::from_value(json!([
+            release("0.36.0", true),
+            release("0.34.0", false),
+            release("0.33.0", false),
+            release("0.10.0", false),
+            {"tag_name": "v0.32.0", "draft": false, "prerelease": false, "assets": []},
+        ]))?;
+        let support_table = parse_support_table(BUNDLED_SUPPORT_TABLE)?;
+
+        let version_urls = release_version_urls(releases, &support_table, "linux64.tar.gz");
+
+        let versions: Vec<_> = version_urls
+            .iter()
+            .map(|version_url| version_url.webdriver_version.to_string())
+            .collect();
+        assert_eq!(versions, ["0.34.0", "0.33.0"]);
+        assert_eq!(
+            version_urls[1].url,
+            "https://example.com/geckodriver-v0.33.0-linux64.tar.gz"
+        );
+        assert!(version_urls[1]
+            .version_req
+            .matches(&semver::Version::new(115, 0, 0)));
+        assert!(!version_urls[1]
+            .version_req
+            .matches(&semver::Version::new(121, 0, 0)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_support_table_or_bundled() -> Result<()> {
+        let newer_md = "<table>\n  <tr>  <td>0.40.0  <td>≥ 4.0  <td>128 ESR  <td>n/a\n";
+        let support_table = support_table_or_bundled(Some(newer_md))?;
+        assert_eq!(support_table.len(), 1);
+        assert_eq!(
+            support_table[0].webdriver_version,
+            semver::Version::new(0, 40, 0)
+        );
+
+        let bundled = parse_support_table(BUNDLED_SUPPORT_TABLE)?;
+        for support_md in [None, Some("not a table")] {
+            let support_table = support_table_or_bundled(support_md)?;
+            assert_eq!(support_table.len(), bundled.len());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_snapshot_version_urls() -> Result<()> {
+        let info = GeckodriverInfo::new("".into(), "".into());
+
+        let version_urls = info.snapshot_version_urls()?;
+
+        assert!(!version_urls.is_empty());
+
+        Ok(())
+    }
 }
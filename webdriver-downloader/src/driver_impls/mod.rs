@@ -1,6 +1,7 @@
 //! This module contains the implementations of the [`WebdriverDownloadInfo`](crate::traits::webdriver_download_info::WebdriverDownloadInfo) trait for the
 //! different webdrivers.
 
+pub mod builder;
 pub mod chromedriver_for_testing_info;
 pub mod chromedriver_info;
 pub mod chromedriver_old_info;
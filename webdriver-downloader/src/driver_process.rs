@@ -0,0 +1,783 @@
+//! Running an installed webdriver as a managed process.
+//!
+//! [`DriverProcess`] launches a driver on a chosen or free port, waits until its `/status`
+//! endpoint reports ready, captures its output and shuts it down when dropped.
+//!
+//! ```no_run
+//! use webdriver_downloader::prelude::*;
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let driver_info = ChromedriverInfo::new_default()?;
+//! let driver = DriverProcess::start_installed(&driver_info, None).await?;
+//!
+//! println!("Driver is listening on {}", driver.url());
+//!
+//! driver.shutdown().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! See [`WebdriverDownloadInfo::ensure_session`](crate::traits::webdriver_download_info::WebdriverDownloadInfo::ensure_session)
+//! for installing a driver and opening a session in one call.
+
+use std::collections::VecDeque;
+use std::ffi::{OsStr, OsString};
+use std::fmt::{self, Debug, Display};
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::Path;
+use std::process::{ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use fantoccini::error::{CmdError, NewSessionError};
+use fantoccini::wd::Capabilities;
+use fantoccini::Client;
+use regex::Regex;
+use semver::Version;
+use serde::Deserialize;
+use tempfile::TempDir;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::traits::installation_info::WebdriverInstallationInfo;
+use crate::traits::verification_info::VerificationConfig;
+use crate::traits::version_req_url_info::VersionReqError;
+
+pub(crate) const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_LOG_LINES: usize = 1000;
+const LOG_TAIL_LINES: usize = 20;
+const LOG_FILE_NAME: &str = "driver.log";
+const MAX_START_ATTEMPTS: usize = 5;
+/// How long to wait for the rest of an exited driver's output.
+const EXITED_OUTPUT_TIMEOUT: Duration = Duration::from_millis(500);
+/// Output of drivers that failed to listen because the port is taken.
+const ADDRESS_IN_USE_MESSAGES: &[&str] = &[
+    "address already in use",
+    "address in use",
+    "port not available",
+];
+
+/// Error that can occur while running a driver process.
+#[derive(thiserror::Error, Debug)]
+pub enum DriverProcessError {
+    #[error("Failed to start driver: {0}")]
+    Spawn(#[from] io::Error),
+    #[error("Failed to query driver status: {0}")]
+    Status(#[from] reqwest::Error),
+    #[error("Failed to parse driver status: {0}")]
+    ParseStatus(#[from] serde_json::Error),
+    #[error("Driver did not become ready within {timeout:?}{logs}")]
+    NotReady {
+        timeout: Duration,
+        logs: DriverLogTail,
+    },
+    #[error("Driver exited before becoming ready ({status}){logs}")]
+    Exited {
+        status: ExitStatus,
+        logs: DriverLogTail,
+    },
+}
+
+impl DriverProcessError {
+    /// Whether the driver failed because its port was already taken.
+    pub fn is_address_in_use(&self) -> bool {
+        match self {
+            DriverProcessError::Exited { logs, .. } | DriverProcessError::NotReady { logs, .. } => {
+                logs.mentions_address_in_use()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Readiness reported by the driver's `/status` endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DriverStatus {
+    pub ready: bool,
+    #[serde(default)]
+    pub message: String,
+    /// Build information. Reported by chromedriver, but not by geckodriver.
+    #[serde(default)]
+    pub build: Option<DriverBuild>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DriverBuild {
+    pub version: String,
+}
+
+impl DriverStatus {
+    /// Driver version reported in [`DriverStatus::build`], if any.
+    pub fn version(&self) -> Option<&str> {
+        self.build.as_ref().map(|build| build.version.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusResponse {
+    value: DriverStatus,
+}
+
+/// Output lines captured from a driver's stdout and stderr.
+///
+/// Only the most recent lines are kept.
+#[derive(Debug, Clone, Default)]
+pub struct DriverLogs(Arc<Mutex<VecDeque<String>>>);
+
+impl DriverLogs {
+    /// Captured lines, oldest first.
+    pub fn lines(&self) -> Vec<String> {
+        self.0.lock().unwrap().iter().cloned().collect()
+    }
+
+    fn push(&self, line: String) {
+        let mut lines = self.0.lock().unwrap();
+        if lines.len() >= MAX_LOG_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    fn capture<R: AsyncRead + Unpin + Send + 'static>(&self, reader: R) -> JoinHandle<()> {
+        let logs = self.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                logs.push(line);
+            }
+        })
+    }
+}
+
+/// The last lines of a driver's output and log file, attached to errors.
+///
+/// Displays as an indented block preceded by a newline, or as nothing if there is no output.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct DriverLogTail {
+    /// Last lines written to stdout and stderr.
+    pub output: Vec<String>,
+    /// Last lines of the driver's log file, if the driver was started with one.
+    pub log_file: Vec<String>,
+}
+
+impl DriverLogTail {
+    pub fn is_empty(&self) -> bool {
+        self.output.is_empty() && self.log_file.is_empty()
+    }
+
+    fn mentions_address_in_use(&self) -> bool {
+        self.output.iter().chain(&self.log_file).any(|line| {
+            let line = line.to_lowercase();
+            ADDRESS_IN_USE_MESSAGES
+                .iter()
+                .any(|message| line.contains(message))
+        })
+    }
+}
+
+impl Display for DriverLogTail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (title, lines) in [
+            ("Driver output", &self.output),
+            ("Driver log", &self.log_file),
+        ] {
+            if lines.is_empty() {
+                continue;
+            }
+
+            write!(f, "\n{}:", title)?;
+            for line in lines {
+                write!(f, "\n    {}", line)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Options for [`DriverProcess::start_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct DriverProcessOptions {
+    /// Port to listen on. A free port is chosen if `None`.
+    pub port: Option<u16>,
+    pub config: VerificationConfig,
+    /// Flag the driver accepts for writing its log to a file, such as chromedriver's `--log-path`.
+    /// The file is created in a temporary directory and included in [`DriverProcess::log_tail`].
+    pub log_file_flag: Option<String>,
+}
+
+/// A running webdriver, listening on `localhost`.
+///
+/// The driver is shut down when this value is dropped.
+/// Use [`DriverProcess::shutdown`] to wait until the driver has exited.
+#[derive(Debug)]
+pub struct DriverProcess {
+    child: Option<Child>,
+    port: u16,
+    logs: DriverLogs,
+    log_readers: Vec<JoinHandle<()>>,
+    log_dir: Option<TempDir>,
+    config: VerificationConfig,
+}
+
+impl DriverProcess {
+    /// Starts the driver at `driver_path` on `port`, or on a free port if `None`,
+    /// and waits until it reports ready.
+    ///
+    /// Uses the default [`VerificationConfig`].
+    pub async fn start<P: AsRef<Path> + Debug>(
+        driver_path: P,
+        port: Option<u16>,
+    ) -> Result<Self, DriverProcessError> {
+        Self::start_with_config(driver_path, port, VerificationConfig::default()).await
+    }
+
+    /// Same as [`DriverProcess::start`], with the timeouts in `config`.
+    pub async fn start_with_config<P: AsRef<Path> + Debug>(
+        driver_path: P,
+        port: Option<u16>,
+        config: VerificationConfig,
+    ) -> Result<Self, DriverProcessError> {
+        let options = DriverProcessOptions {
+            port,
+            config,
+            ..Default::default()
+        };
+
+        Self::start_with_options(driver_path, options).await
+    }
+
+    /// Same as [`DriverProcess::start`], with all [`DriverProcessOptions`].
+    ///
+    /// If no port is given and the driver exits because the chosen port was taken in the meantime,
+    /// the driver is restarted on another port.
+    #[tracing::instrument]
+    pub async fn start_with_options<P: AsRef<Path> + Debug>(
+        driver_path: P,
+        options: DriverProcessOptions,
+    ) -> Result<Self, DriverProcessError> {
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+            let port = options.port.unwrap_or_else(get_random_available_port);
+            let mut driver = Self::spawn(driver_path.as_ref(), port, &options)?;
+
+            match driver.wait_ready(options.config.startup_timeout).await {
+                Ok(_) => return Ok(driver),
+                Err(e)
+                    if options.port.is_none()
+                        && attempts < MAX_START_ATTEMPTS
+                        && e.is_address_in_use() =>
+                {
+                    tracing::debug!(
+                        "Port {} is already in use, retrying with another port",
+                        port
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn spawn(
+        driver_path: &Path,
+        port: u16,
+        options: &DriverProcessOptions,
+    ) -> Result<Self, DriverProcessError> {
+        let mut command = Command::new(OsStr::new(driver_path));
+        command.arg(format!("--port={}", port));
+
+        let log_dir = match &options.log_file_flag {
+            Some(flag) => {
+                let log_dir = TempDir::new()?;
+                let mut arg = OsString::from(format!("{}=", flag));
+                arg.push(log_dir.path().join(LOG_FILE_NAME));
+                command.arg(arg);
+                Some(log_dir)
+            }
+            None => None,
+        };
+
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let logs = DriverLogs::default();
+        let mut log_readers = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            log_readers.push(logs.capture(stdout));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            log_readers.push(logs.capture(stderr));
+        }
+
+        Ok(DriverProcess {
+            child: Some(child),
+            port,
+            logs,
+            log_readers,
+            log_dir,
+            config: options.config,
+        })
+    }
+
+    /// Starts the driver installed at [`WebdriverInstallationInfo::driver_install_path`].
+    pub async fn start_installed(
+        driver_info: &(impl WebdriverInstallationInfo + ?Sized),
+        port: Option<u16>,
+    ) -> Result<Self, DriverProcessError> {
+        Self::start(driver_info.driver_install_path(), port).await
+    }
+
+    /// Timeouts this driver was started with.
+    pub fn config(&self) -> &VerificationConfig {
+        &self.config
+    }
+
+    /// Port the driver is listening on.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Url of the driver, to be passed to [`fantoccini::ClientBuilder::connect`].
+    pub fn url(&self) -> String {
+        format!("http://localhost:{}", self.port)
+    }
+
+    /// Lines the driver has written to stdout and stderr so far.
+    pub fn logs(&self) -> Vec<String> {
+        self.logs.lines()
+    }
+
+    /// Last lines of the driver's output and log file, for error reporting.
+    pub fn log_tail(&self) -> DriverLogTail {
+        let output = self.logs.lines();
+        let output = output[output.len().saturating_sub(LOG_TAIL_LINES)..].to_vec();
+
+        let log_file = self
+            .log_dir
+            .as_ref()
+            .and_then(|dir| fs::read(dir.path().join(LOG_FILE_NAME)).ok())
+            .map(|content| {
+                let content = String::from_utf8_lossy(&content);
+                let lines = content.lines().collect::<Vec<_>>();
+                lines[lines.len().saturating_sub(LOG_TAIL_LINES)..]
+                    .iter()
+                    .map(|line| line.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        DriverLogTail { output, log_file }
+    }
+
+    /// Queries the driver's `/status` endpoint.
+    pub async fn status(&self) -> Result<DriverStatus, DriverProcessError> {
+        let response = reqwest::get(format!("{}/status", self.url()))
+            .await?
+            .text()
+            .await?;
+
+        let response: StatusResponse = serde_json::from_str(&response)?;
+
+        Ok(response.value)
+    }
+
+    /// Polls [`DriverProcess::status`] every [`VerificationConfig::poll_interval`]
+    /// until the driver reports ready, up to `timeout`.
+    ///
+    /// Fails immediately if the driver exits.
+    #[tracing::instrument(skip(self))]
+    pub async fn wait_ready(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<DriverStatus, DriverProcessError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            self.check_running().await?;
+
+            match tokio::time::timeout_at(deadline, self.status()).await {
+                Ok(Ok(status)) if status.ready => {
+                    // Another process listening on the same port may have answered.
+                    self.check_running().await?;
+                    return Ok(status);
+                }
+                Ok(Ok(status)) => tracing::trace!("Driver not ready: {}", status.message),
+                Ok(Err(e)) => tracing::trace!("Driver status unavailable: {}", e),
+                Err(_) => {}
+            }
+
+            if Instant::now() >= deadline {
+                return Err(DriverProcessError::NotReady {
+                    timeout,
+                    logs: self.log_tail(),
+                });
+            }
+
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+
+    /// Returns [`DriverProcessError::Exited`] if the driver has exited.
+    async fn check_running(&mut self) -> Result<(), DriverProcessError> {
+        let Some(child) = self.child.as_mut() else {
+            return Ok(());
+        };
+
+        let Some(status) = child.try_wait()? else {
+            return Ok(());
+        };
+
+        for log_reader in self.log_readers.drain(..) {
+            let _ = tokio::time::timeout(EXITED_OUTPUT_TIMEOUT, log_reader).await;
+        }
+
+        Err(DriverProcessError::Exited {
+            status,
+            logs: self.log_tail(),
+        })
+    }
+
+    /// Opens a new session with `capabilities`, retrying every [`VerificationConfig::poll_interval`]
+    /// until [`VerificationConfig::session_timeout`] elapses.
+    #[tracing::instrument(skip(self))]
+    pub async fn connect(
+        &self,
+        capabilities: Option<Capabilities>,
+    ) -> Result<Client, NewSessionError> {
+        let deadline = Instant::now() + self.config.session_timeout;
+        #[cfg(feature = "native-tls")]
+        let mut new_client = fantoccini::ClientBuilder::native();
+        #[cfg(feature = "rustls-tls")]
+        let mut new_client = fantoccini::ClientBuilder::rustls();
+        if let Some(capabilities) = capabilities {
+            new_client.capabilities(capabilities);
+        }
+
+        loop {
+            let error =
+                match tokio::time::timeout_at(deadline, new_client.connect(&self.url())).await {
+                    Ok(Ok(client)) => return Ok(client),
+                    Ok(Err(e)) => e,
+                    Err(_) => NewSessionError::Lost(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        format!(
+                            "session was not created within {:?}",
+                            self.config.session_timeout
+                        ),
+                    )),
+                };
+
+            if Instant::now() >= deadline {
+                return Err(error);
+            }
+
+            tracing::trace!("Failed to create session: {}", error);
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+
+    /// Asks the driver to exit, killing it if it does not exit in time.
+    pub async fn shutdown(mut self) -> io::Result<()> {
+        match self.child.take() {
+            Some(child) => shutdown_child(child).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for DriverProcess {
+    fn drop(&mut self) {
+        let Some(mut child) = self.child.take() else {
+            return;
+        };
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    if let Err(e) = shutdown_child(child).await {
+                        tracing::warn!("Failed to shut down driver: {}", e);
+                    }
+                });
+            }
+            Err(_) => {
+                if let Err(e) = child.start_kill() {
+                    tracing::warn!("Failed to kill driver: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// A [`fantoccini::Client`] bound to the [`DriverProcess`] it is connected to.
+///
+/// Dereferences to the client. The driver is shut down when this value is dropped;
+/// use [`DriverSession::close`] to end the session first.
+#[derive(Debug)]
+pub struct DriverSession {
+    client: Client,
+    driver: DriverProcess,
+}
+
+impl DriverSession {
+    pub fn new(client: Client, driver: DriverProcess) -> Self {
+        DriverSession { client, driver }
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn driver(&self) -> &DriverProcess {
+        &self.driver
+    }
+
+    /// Closes the session, then shuts down the driver.
+    pub async fn close(self) -> Result<(), CmdError> {
+        let close_result = self.client.close().await;
+
+        if let Err(e) = self.driver.shutdown().await {
+            tracing::warn!("Failed to shut down driver: {}", e);
+        }
+
+        close_result
+    }
+}
+
+impl Deref for DriverSession {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+async fn shutdown_child(mut child: Child) -> io::Result<()> {
+    #[cfg(target_family = "unix")]
+    if let Some(pid) = child.id() {
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid;
+
+        if kill(Pid::from_raw(pid as i32), Signal::SIGTERM).is_ok()
+            && tokio::time::timeout(SHUTDOWN_TIMEOUT, child.wait())
+                .await
+                .is_ok()
+        {
+            return Ok(());
+        }
+    }
+
+    child.kill().await
+}
+
+/// Version the driver at `driver_path` reports with `--version`.
+#[tracing::instrument]
+pub fn reported_version(driver_path: &Path) -> Result<Version, VersionReqError> {
+    let re = Regex::new(r"([0-9]+\.[0-9.]+)").expect("Failed to parse regex.");
+    let output = std::process::Command::new(driver_path)
+        .arg("--version")
+        .stderr(Stdio::piped())
+        .output()?;
+
+    let version_output = String::from_utf8_lossy(&output.stdout);
+    tracing::trace!("Driver version string: {}", version_output);
+    let version_string = re
+        .captures(&version_output)
+        .and_then(|captures| captures.get(1))
+        .ok_or_else(|| VersionReqError::RegexError(version_output.to_string()))?;
+
+    lenient_semver::parse(version_string.as_str()).map_err(|e| e.owned().into())
+}
+
+pub(crate) fn get_random_available_port() -> u16 {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener};
+
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 0);
+    let listener = TcpListener::bind(addr).unwrap();
+    listener.local_addr().unwrap().port()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_get_random_available_port() -> Result<()> {
+        let port = get_random_available_port();
+
+        // Check if the port number is within the valid range
+        assert!(port > 0, "Port number should be within the valid range");
+
+        // Check if the port is actually available
+        let addr = format!("127.0.0.1:{}", port);
+        TcpListener::bind(addr)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_status_responses() -> Result<()> {
+        let chromedriver = r#"{"value":{"build":{"version":"120.0.6099.109"},"message":"ChromeDriver ready for new sessions.","os":{"arch":"x86_64","name":"Linux","version":"6.1.0"},"ready":true}}"#;
+        let geckodriver = r#"{"value":{"message":"","ready":true}}"#;
+
+        let status: StatusResponse = serde_json::from_str(chromedriver)?;
+        assert!(status.value.ready);
+        assert_eq!(status.value.message, "ChromeDriver ready for new sessions.");
+        assert_eq!(status.value.version(), Some("120.0.6099.109"));
+
+        let status: StatusResponse = serde_json::from_str(geckodriver)?;
+        assert!(status.value.ready);
+        assert_eq!(status.value.version(), None);
+
+        Ok(())
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn parses_reported_versions() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir()?;
+        let driver_path = dir.path().join("driver");
+        let cases = [
+            ("geckodriver 0.35.0 (9f0a0036bea4 2024-08-03 07:11 +0000)", "0.35.0"),
+            (
+                "ChromeDriver 120.0.6099.109 (3419140ab665596f21b385ce136419fde0924272-refs/branch-heads/6099@{#1483})",
+                "120.0.6099.109",
+            ),
+        ];
+
+        for (output, version) in cases {
+            std::fs::write(&driver_path, format!("#!/bin/sh\necho '{}'\n", output))?;
+            std::fs::set_permissions(&driver_path, std::fs::Permissions::from_mode(0o755))?;
+
+            assert_eq!(
+                reported_version(&driver_path)?,
+                lenient_semver::parse(version).unwrap()
+            );
+        }
+
+        std::fs::write(&driver_path, "#!/bin/sh\necho 'no version'\n")?;
+        assert!(matches!(
+            reported_version(&driver_path),
+            Err(VersionReqError::RegexError(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn log_tail_display() {
+        assert_eq!(DriverLogTail::default().to_string(), "");
+
+        let tail = DriverLogTail {
+            output: vec!["Starting ChromeDriver".to_string()],
+            log_file: vec!["[SEVERE]: crashed".to_string()],
+        };
+
+        assert_eq!(
+            tail.to_string(),
+            "\nDriver output:\n    Starting ChromeDriver\nDriver log:\n    [SEVERE]: crashed"
+        );
+    }
+
+    #[test]
+    fn logs_keep_most_recent_lines() {
+        let logs = DriverLogs::default();
+        for i in 0..MAX_LOG_LINES + 10 {
+            logs.push(i.to_string());
+        }
+
+        let lines = logs.lines();
+        assert_eq!(lines.len(), MAX_LOG_LINES);
+        assert_eq!(lines.first().map(String::as_str), Some("10"));
+    }
+
+    #[cfg(target_family = "unix")]
+    #[tokio::test]
+    async fn start_gives_up_after_startup_timeout() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new()?;
+        let driver_path = dir.path().join("driver");
+        // Stays alive without ever serving `/status`.
+        std::fs::write(&driver_path, "#!/bin/sh\nexec sleep 30\n")?;
+        std::fs::set_permissions(&driver_path, std::fs::Permissions::from_mode(0o755))?;
+
+        let startup_timeout = Duration::from_millis(500);
+        let config = VerificationConfig {
+            startup_timeout,
+            ..Default::default()
+        };
+
+        let started = std::time::Instant::now();
+        let result = DriverProcess::start_with_config(&driver_path, None, config).await;
+        let elapsed = started.elapsed();
+
+        assert!(matches!(result, Err(DriverProcessError::NotReady { .. })));
+        assert!(elapsed >= startup_timeout);
+        assert!(elapsed < Duration::from_secs(10));
+
+        Ok(())
+    }
+
+    #[cfg(target_family = "unix")]
+    #[tokio::test]
+    async fn start_reports_early_exit() {
+        let config = VerificationConfig {
+            startup_timeout: Duration::from_secs(30),
+            ..Default::default()
+        };
+
+        let started = std::time::Instant::now();
+        let result = DriverProcess::start_with_config("false", None, config).await;
+
+        assert!(matches!(result, Err(DriverProcessError::Exited { .. })));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(target_family = "unix")]
+    #[tokio::test]
+    async fn start_retries_when_address_in_use() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new()?;
+        let attempts_path = dir.path().join("attempts");
+        let driver_path = dir.path().join("driver");
+        std::fs::write(
+            &driver_path,
+            format!(
+                "#!/bin/sh\necho attempt >> '{}'\necho 'bind() failed: Address already in use (98)' >&2\nexit 1\n",
+                attempts_path.display()
+            ),
+        )?;
+        std::fs::set_permissions(&driver_path, std::fs::Permissions::from_mode(0o755))?;
+
+        let result = DriverProcess::start(&driver_path, None).await;
+
+        assert!(matches!(&result, Err(e) if e.is_address_in_use()));
+        let attempts = std::fs::read_to_string(&attempts_path)?;
+        assert_eq!(attempts.lines().count(), MAX_START_ATTEMPTS);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn start_fails_for_missing_driver() {
+        let result = DriverProcess::start("no_such_driver", None).await;
+
+        assert!(matches!(result, Err(DriverProcessError::Spawn(_))));
+    }
+}
@@ -0,0 +1,265 @@
+//! Detects the platform of an executable from its ELF, Mach-O or PE header.
+
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::platform::{Arch, Os, Platform};
+
+/// Number of bytes read from the start of an executable. Enough for the headers of every supported format.
+const HEADER_LEN: u64 = 4096;
+
+const ELF_MAGIC: &[u8] = b"\x7fELF";
+const MACHO_32_MAGIC: u32 = 0xfeed_face;
+const MACHO_64_MAGIC: u32 = 0xfeed_facf;
+const MACHO_FAT_MAGIC: u32 = 0xcafe_babe;
+const PE_MAGIC: &[u8] = b"PE\0\0";
+
+/// Operating system and architectures of an executable.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct ExecutableFormat {
+    pub os: Os,
+    /// Architectures the executable has code for. More than one for macOS universal binaries,
+    /// empty if the architecture is not one of [`Arch`].
+    pub archs: Vec<Arch>,
+}
+
+impl ExecutableFormat {
+    /// Reads the header of the executable at `path`. Returns `None` if it is not an ELF, Mach-O or PE executable.
+    pub fn read(path: &Path) -> io::Result<Option<Self>> {
+        let mut header = Vec::new();
+        File::open(path)?
+            .take(HEADER_LEN)
+            .read_to_end(&mut header)?;
+
+        Ok(Self::parse(&header))
+    }
+
+    pub fn parse(header: &[u8]) -> Option<Self> {
+        if header.starts_with(ELF_MAGIC) {
+            parse_elf(header)
+        } else if header.starts_with(b"MZ") {
+            parse_pe(header)
+        } else {
+            parse_macho(header)
+        }
+    }
+
+    /// Whether the executable can run on `platform`.
+    /// 32-bit Windows executables also run on 64-bit Windows, which old chromedriver relies on.
+    pub fn runs_on(&self, platform: Platform) -> bool {
+        if self.os != platform.os {
+            return false;
+        }
+
+        self.archs.contains(&platform.arch)
+            || (platform.os == Os::Windows
+                && platform.arch == Arch::X86_64
+                && self.archs.contains(&Arch::X86))
+    }
+}
+
+impl Display for ExecutableFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let archs = match self.archs.as_slice() {
+            [] => "an unknown architecture".to_string(),
+            archs => archs
+                .iter()
+                .map(Arch::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+
+        write!(f, "{} executable for {}", self.os, archs)
+    }
+}
+
+fn parse_elf(header: &[u8]) -> Option<ExecutableFormat> {
+    let big_endian = *header.get(5)? == 2;
+    let machine = read_u16(header, 18, big_endian)?;
+
+    let arch = match machine {
+        3 => Some(Arch::X86),
+        62 => Some(Arch::X86_64),
+        183 => Some(Arch::Aarch64),
+        _ => None,
+    };
+
+    Some(ExecutableFormat {
+        os: Os::Linux,
+        archs: arch.into_iter().collect(),
+    })
+}
+
+fn parse_pe(header: &[u8]) -> Option<ExecutableFormat> {
+    let pe_offset = read_u32(header, 0x3c, false)? as usize;
+    if header.get(pe_offset..pe_offset + PE_MAGIC.len())? != PE_MAGIC {
+        return None;
+    }
+    let machine = read_u16(header, pe_offset + PE_MAGIC.len(), false)?;
+
+    let arch = match machine {
+        0x014c => Some(Arch::X86),
+        0x8664 => Some(Arch::X86_64),
+        0xaa64 => Some(Arch::Aarch64),
+        _ => None,
+    };
+
+    Some(ExecutableFormat {
+        os: Os::Windows,
+        archs: arch.into_iter().collect(),
+    })
+}
+
+fn parse_macho(header: &[u8]) -> Option<ExecutableFormat> {
+    let cpu_types = match read_u32(header, 0, true)? {
+        // Universal binaries list their architectures in big endian.
+        MACHO_FAT_MAGIC => {
+            let count = read_u32(header, 4, true)? as usize;
+            (0..count)
+                .map(|index| read_u32(header, 8 + index * 20, true))
+                .collect::<Option<Vec<_>>>()?
+        }
+        magic if magic.swap_bytes() == MACHO_32_MAGIC || magic.swap_bytes() == MACHO_64_MAGIC => {
+            vec![read_u32(header, 4, false)?]
+        }
+        MACHO_32_MAGIC | MACHO_64_MAGIC => vec![read_u32(header, 4, true)?],
+        _ => return None,
+    };
+
+    let archs = cpu_types
+        .into_iter()
+        .filter_map(|cpu_type| match cpu_type {
+            0x0000_0007 => Some(Arch::X86),
+            0x0100_0007 => Some(Arch::X86_64),
+            0x0100_000c => Some(Arch::Aarch64),
+            _ => None,
+        })
+        .collect();
+
+    Some(ExecutableFormat {
+        os: Os::MacOs,
+        archs,
+    })
+}
+
+fn read_u16(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u16> {
+    let bytes: [u8; 2] = bytes.get(offset..offset + 2)?.try_into().ok()?;
+    Some(match big_endian {
+        true => u16::from_be_bytes(bytes),
+        false => u16::from_le_bytes(bytes),
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let bytes: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+    Some(match big_endian {
+        true => u32::from_be_bytes(bytes),
+        false => u32::from_le_bytes(bytes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::platform::{Arch, Os, Platform};
+
+    use super::ExecutableFormat;
+
+    fn elf(machine: u16) -> Vec<u8> {
+        let mut header = b"\x7fELF\x02\x01\x01".to_vec();
+        header.resize(18, 0);
+        header.extend(machine.to_le_bytes());
+        header
+    }
+
+    fn pe(machine: u16) -> Vec<u8> {
+        let mut header = b"MZ".to_vec();
+        header.resize(0x3c, 0);
+        header.extend(0x80u32.to_le_bytes());
+        header.resize(0x80, 0);
+        header.extend(b"PE\0\0");
+        header.extend(machine.to_le_bytes());
+        header
+    }
+
+    fn macho(cpu_type: u32) -> Vec<u8> {
+        let mut header = 0xfeed_facfu32.to_le_bytes().to_vec();
+        header.extend(cpu_type.to_le_bytes());
+        header
+    }
+
+    fn fat_macho(cpu_types: &[u32]) -> Vec<u8> {
+        let mut header = 0xcafe_babeu32.to_be_bytes().to_vec();
+        header.extend((cpu_types.len() as u32).to_be_bytes());
+        for cpu_type in cpu_types {
+            header.extend(cpu_type.to_be_bytes());
+            header.extend([0; 16]);
+        }
+        header
+    }
+
+    #[test]
+    fn parses_headers() {
+        let format = |os, archs: &[Arch]| {
+            Some(ExecutableFormat {
+                os,
+                archs: archs.to_vec(),
+            })
+        };
+
+        assert_eq!(
+            ExecutableFormat::parse(&elf(62)),
+            format(Os::Linux, &[Arch::X86_64])
+        );
+        assert_eq!(
+            ExecutableFormat::parse(&elf(183)),
+            format(Os::Linux, &[Arch::Aarch64])
+        );
+        assert_eq!(
+            ExecutableFormat::parse(&pe(0x014c)),
+            format(Os::Windows, &[Arch::X86])
+        );
+        assert_eq!(
+            ExecutableFormat::parse(&macho(0x0100_000c)),
+            format(Os::MacOs, &[Arch::Aarch64])
+        );
+        assert_eq!(
+            ExecutableFormat::parse(&fat_macho(&[0x0100_0007, 0x0100_000c])),
+            format(Os::MacOs, &[Arch::X86_64, Arch::Aarch64])
+        );
+        assert_eq!(ExecutableFormat::parse(&elf(40)), format(Os::Linux, &[]));
+
+        assert_eq!(ExecutableFormat::parse(b"<html>Not Found</html>"), None);
+        assert_eq!(ExecutableFormat::parse(b"MZ"), None);
+        assert_eq!(ExecutableFormat::parse(b""), None);
+    }
+
+    #[test]
+    fn checks_platform() {
+        let linux_x86_64 = Platform::new(Os::Linux, Arch::X86_64);
+        let windows_x86_64 = Platform::new(Os::Windows, Arch::X86_64);
+
+        let elf = ExecutableFormat::parse(&elf(62)).unwrap();
+        assert!(elf.runs_on(linux_x86_64));
+        assert!(!elf.runs_on(Platform::new(Os::Linux, Arch::Aarch64)));
+        assert!(!elf.runs_on(windows_x86_64));
+
+        let pe_x86 = ExecutableFormat::parse(&pe(0x014c)).unwrap();
+        assert!(pe_x86.runs_on(windows_x86_64));
+        assert!(!pe_x86.runs_on(Platform::new(Os::Windows, Arch::Aarch64)));
+
+        let universal = ExecutableFormat::parse(&fat_macho(&[0x0100_0007, 0x0100_000c])).unwrap();
+        assert!(universal.runs_on(Platform::new(Os::MacOs, Arch::Aarch64)));
+        assert!(universal.runs_on(Platform::new(Os::MacOs, Arch::X86_64)));
+    }
+
+    #[test]
+    fn reads_current_executable() {
+        let format = ExecutableFormat::read(&std::env::current_exe().unwrap())
+            .unwrap()
+            .unwrap();
+
+        assert!(format.runs_on(Platform::current()));
+    }
+}
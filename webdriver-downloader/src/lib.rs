@@ -29,6 +29,26 @@
 //! it to the default location.
 //! See [`default_install_path`](os_specific::chromedriver_old::default_driver_path)s for default install locations.
 //!
+//! ## Opening a session with an installed driver
+//!
+//! ```no_run
+//! use webdriver_downloader::prelude::*;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let chromedriver_info = ChromedriverInfo::new_default()?;
+//!
+//!     let session = chromedriver_info.ensure_session(5).await?;
+//!     session.goto("https://www.example.com").await?;
+//!
+//!     session.close().await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The session is bound to a [`DriverProcess`](driver_process::DriverProcess), which is shut down with it.
+//!
 //! # Implementing your own webdriver
 //!
 //! See [`traits`] for more information on how to implement your own webdriver.
@@ -51,8 +71,18 @@ To learn more about features in Cargo, see <https://doc.rust-lang.org/cargo/refe
 To find out which crate(s) enabled the mutually exclusive features of `webdriver-downloader`, run `cargo tree -e features -i webdriver-downloader`.
 "#);
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod driver_impls;
+pub mod driver_process;
+mod executable_format;
+mod local_server;
+pub mod metadata_cache;
 pub mod os_specific;
+pub mod platform;
+#[cfg(feature = "provision")]
+pub mod provision;
+pub mod registry;
 pub mod traits;
 
 /// Re-exported driver implementations, traits, errors and os_specific data.
@@ -60,17 +90,35 @@ pub mod traits;
 /// This module is intended to be used as a prelude, and contains all the
 /// necessary imports for using library provided driver implementations.
 pub mod prelude {
+    pub use crate::driver_impls::builder::{BuildError, DriverInfoBuilder};
     pub use crate::driver_impls::{
         chromedriver_info::ChromedriverInfo, geckodriver_info::GeckodriverInfo,
     };
+    pub use crate::driver_process::{
+        DriverLogTail, DriverProcess, DriverProcessError, DriverProcessOptions, DriverSession,
+        DriverStatus,
+    };
+    pub use crate::metadata_cache::MetadataCache;
     pub use crate::os_specific;
+    pub use crate::platform::{Arch, Os, ParsePlatformError, Platform};
+    pub use crate::registry::{DriverFactory, DriverOptions, DriverRegistry, RegisteredDriver};
     pub use crate::traits::installation_info::{
         AddExecutePermissionError, InstallationError, WebdriverInstallationInfo,
     };
+    pub use crate::traits::offline_verification_info::{
+        OfflineVerificationError, OfflineVerificationInfo,
+    };
     pub use crate::traits::url_info::{UrlError, WebdriverUrlInfo, WebdriverVersionUrl};
-    pub use crate::traits::verification_info::{VerificationError, WebdriverVerificationInfo};
+    pub use crate::traits::verification_info::{
+        BrowserOptions, DriverStatusReport, VerificationConfig, VerificationError,
+        VerificationLevel, VerificationTarget, WebdriverVerificationInfo,
+    };
+    pub use crate::traits::verification_scenario::{
+        ExecuteScriptScenario, NavigateScenario, PageMarker, ScreenshotScenario,
+        VerificationScenario, WindowSizeScenario,
+    };
     pub use crate::traits::version_req_url_info::{VersionReqError, VersionReqUrlInfo};
     pub use crate::traits::webdriver_download_info::{
-        WebdriverDownloadError, WebdriverDownloadInfo,
+        InstallationPlan, WebdriverDownloadError, WebdriverDownloadInfo,
     };
 }
@@ -0,0 +1,103 @@
+//! Minimal http server for serving verification pages on localhost.
+
+use std::io;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+const MAX_REQUEST_HEAD_SIZE: usize = 16 * 1024;
+
+/// Delay before accepting again after a failure, such as running out of file descriptors.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Serves a single html page for every request, until dropped.
+#[derive(Debug)]
+pub(crate) struct LocalPageServer {
+    url: String,
+    task: JoinHandle<()>,
+}
+
+impl LocalPageServer {
+    #[tracing::instrument(skip(html))]
+    pub(crate) async fn start(html: String) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let url = format!("http://127.0.0.1:{}/", listener.local_addr()?.port());
+
+        let html = Arc::new(html);
+        let task = tokio::spawn(async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        tracing::warn!("Failed to accept connection: {}", e);
+                        tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                        continue;
+                    }
+                };
+
+                let html = html.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve(stream, &html).await {
+                        tracing::trace!("Failed to serve page: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(LocalPageServer { url, task })
+    }
+
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Drop for LocalPageServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn serve(mut stream: TcpStream, html: &str) -> io::Result<()> {
+    // The request itself is irrelevant, but it has to be read before responding.
+    let mut head = Vec::new();
+    let mut buf = [0; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 || head.len() > MAX_REQUEST_HEAD_SIZE {
+            break;
+        }
+        head.extend_from_slice(&buf[..read]);
+    }
+
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        html.len(),
+        html
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::LocalPageServer;
+
+    #[tokio::test]
+    async fn serves_page() -> Result<()> {
+        let html = "<html><body>test page</body></html>";
+        let server = LocalPageServer::start(html.to_string()).await?;
+
+        let body = reqwest::get(server.url()).await?.text().await?;
+
+        assert_eq!(body, html);
+
+        Ok(())
+    }
+}
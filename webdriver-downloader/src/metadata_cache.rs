@@ -0,0 +1,456 @@
+//! On-disk cache for version metadata, such as Chrome for Testing's known good versions.
+//!
+//! Responses are reused without a request until their ttl expires.
+//! After that, they are revalidated with `If-None-Match`/`If-Modified-Since`,
+//! so unchanged metadata is not downloaded again.
+//! If that fails, the expired response is used.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{self, HeaderValue};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+use crate::traits::url_info::{UrlError, WebdriverVersionUrl};
+
+/// How long cached metadata is used without revalidating it, by default.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Parsed version lists by url and parser key, with the hash of the body they were parsed from.
+type ParsedVersionUrls = BTreeMap<(String, String), (u64, Vec<WebdriverVersionUrl>)>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// Seconds since the unix epoch when the entry was last fetched or revalidated.
+    fetched_at: u64,
+    body: String,
+}
+
+/// Cache of http responses, stored as one json file per url.
+/// Clones share the version lists parsed by [`get_version_urls`](Self::get_version_urls).
+#[derive(Clone)]
+pub struct MetadataCache {
+    /// Directory to store responses in. Caching is disabled if `None`.
+    pub dir: Option<PathBuf>,
+    pub ttl: Duration,
+    parsed: Arc<Mutex<ParsedVersionUrls>>,
+}
+
+impl Debug for MetadataCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetadataCache")
+            .field("dir", &self.dir)
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for MetadataCache {
+    fn eq(&self, other: &Self) -> bool {
+        self.dir == other.dir && self.ttl == other.ttl
+    }
+}
+
+impl Eq for MetadataCache {}
+
+impl Default for MetadataCache {
+    /// Caches in [`MetadataCache::default_dir`] for [`DEFAULT_TTL`].
+    fn default() -> Self {
+        MetadataCache {
+            dir: Self::default_dir(),
+            ttl: DEFAULT_TTL,
+            parsed: Default::default(),
+        }
+    }
+}
+
+impl MetadataCache {
+    pub fn new(dir: PathBuf, ttl: Duration) -> Self {
+        MetadataCache {
+            dir: Some(dir),
+            ttl,
+            parsed: Default::default(),
+        }
+    }
+
+    /// Cache that always sends unconditional requests and stores nothing.
+    pub fn disabled() -> Self {
+        MetadataCache {
+            dir: None,
+            ttl: Duration::ZERO,
+            parsed: Default::default(),
+        }
+    }
+
+    /// `HOME_DIR/.cache/webdriver-downloader`.
+    pub fn default_dir() -> Option<PathBuf> {
+        home::home_dir().map(|home_dir| home_dir.join(".cache").join("webdriver-downloader"))
+    }
+
+    /// Sends `request` and returns the response body, using the cached body if it is still fresh or unchanged.
+    /// If the request fails, an expired cached body is used instead.
+    /// Failing to read or write the cache is logged and otherwise ignored.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_text(&self, request: RequestBuilder) -> Result<String, reqwest::Error> {
+        self.fetch(request).await.map(|(_, body)| body)
+    }
+
+    /// [`get_text`](Self::get_text), parsed with `parse`.
+    /// Unless caching is disabled, the newest list for each url and `key` is kept with this cache,
+    /// so unchanged metadata is only parsed once.
+    /// `key` tells apart lists parsed differently from the same url, such as for different platforms.
+    pub async fn get_version_urls(
+        &self,
+        request: RequestBuilder,
+        key: &str,
+        parse: impl FnOnce(&str) -> Result<Vec<WebdriverVersionUrl>, UrlError>,
+    ) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+        let (url, body) = self.fetch(request).await?;
+        if self.dir.is_none() {
+            return parse(&body);
+        }
+
+        let memo_key = (url, key.to_string());
+        let body_hash = hash(&body);
+        let mut parsed = self.parsed.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((hash, version_urls)) = parsed.get(&memo_key) {
+            if *hash == body_hash {
+                return Ok(version_urls.clone());
+            }
+        }
+
+        let version_urls = parse(&body)?;
+        parsed.insert(memo_key, (body_hash, version_urls.clone()));
+
+        Ok(version_urls)
+    }
+
+    /// Returns the url and body of the response to `request`.
+    async fn fetch(&self, request: RequestBuilder) -> Result<(String, String), reqwest::Error> {
+        let (client, request) = request.build_split();
+        let mut request = request?;
+        let url = request.url().to_string();
+
+        let Some(path) = self.entry_path(&url) else {
+            let body = client
+                .execute(request)
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+            return Ok((url, body));
+        };
+
+        let cached = read_entry(&path).filter(|entry| entry.url == url);
+
+        if let Some(entry) = &cached {
+            if now().saturating_sub(entry.fetched_at) < self.ttl.as_secs() {
+                tracing::debug!("Using cached {}", entry.url);
+                return Ok((url, entry.body.clone()));
+            }
+
+            let headers = request.headers_mut();
+            if let Some(etag) = entry.etag.as_deref().and_then(header_value) {
+                headers.insert(header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = entry.last_modified.as_deref().and_then(header_value) {
+                headers.insert(header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let downloaded = async {
+            let response = client.execute(request).await?;
+
+            if let (StatusCode::NOT_MODIFIED, Some(entry)) = (response.status(), &cached) {
+                tracing::debug!("{} is not modified", url);
+                return Ok(CacheEntry {
+                    fetched_at: now(),
+                    ..entry.clone()
+                });
+            }
+
+            let response = response.error_for_status()?;
+            let header_string = |name| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            };
+            let etag = header_string(header::ETAG);
+            let last_modified = header_string(header::LAST_MODIFIED);
+            let body = response.text().await?;
+
+            Ok(CacheEntry {
+                url: url.clone(),
+                etag,
+                last_modified,
+                fetched_at: now(),
+                body,
+            })
+        }
+        .await;
+
+        match (downloaded, cached) {
+            (Ok(entry), _) => {
+                write_entry(&path, &entry);
+                Ok((url, entry.body))
+            }
+            (Err(e), Some(entry)) => {
+                tracing::warn!("Failed to revalidate {}, using expired cache: {}", url, e);
+                Ok((url, entry.body))
+            }
+            (Err(e), None) => Err(e),
+        }
+    }
+
+    /// Cached body for `url`, even if its ttl has expired. Sends no request.
+    pub fn cached_text(&self, url: &str) -> Option<String> {
+        let url = reqwest::Url::parse(url).ok()?;
+        let path = self.entry_path(url.as_str())?;
+
+        read_entry(&path)
+            .filter(|entry| entry.url == url.as_str())
+            .map(|entry| entry.body)
+    }
+
+    fn entry_path(&self, url: &str) -> Option<PathBuf> {
+        let file_name: String = url
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.json", file_name)))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn hash(body: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn header_value(value: &str) -> Option<HeaderValue> {
+    HeaderValue::from_str(value).ok()
+}
+
+fn read_entry(path: &Path) -> Option<CacheEntry> {
+    let contents = std::fs::read_to_string(path).ok()?;
+
+    match serde_json::from_str(&contents) {
+        Ok(entry) => Some(entry),
+        Err(e) => {
+            tracing::warn!("Ignoring invalid cache entry {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn write_entry(path: &Path, entry: &CacheEntry) {
+    let write = || -> anyhow::Result<()> {
+        let dir = path.parent().expect("Cache entry has no parent directory");
+        std::fs::create_dir_all(dir)?;
+
+        let mut file = NamedTempFile::new_in(dir)?;
+        file.write_all(&serde_json::to_vec(entry)?)?;
+        file.persist(path)?;
+
+        Ok(())
+    };
+
+    if let Err(e) = write() {
+        tracing::warn!("Failed to write cache entry {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use anyhow::Result;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::MetadataCache;
+
+    /// Serves `body` with an etag, or 304 if the request has a matching `If-None-Match`.
+    /// Returns the url and the number of full responses sent.
+    async fn start_server(body: &'static str) -> Result<(String, Arc<AtomicUsize>)> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let url = format!(
+            "http://127.0.0.1:{}/versions.json",
+            listener.local_addr()?.port()
+        );
+        let full_responses = Arc::new(AtomicUsize::new(0));
+
+        let counter = full_responses.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = vec![0; 4096];
+                let read = stream.read(&mut buf).await.unwrap_or(0);
+                let head = String::from_utf8_lossy(&buf[..read]).to_lowercase();
+
+                let response = if head.contains("if-none-match: \"v1\"") {
+                    "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n".to_string()
+                } else {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    format!(
+                        "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        Ok((url, full_responses))
+    }
+
+    #[tokio::test]
+    async fn fresh_entries_skip_requests() -> Result<()> {
+        let (url, full_responses) = start_server("{}").await?;
+        let dir = tempfile::tempdir()?;
+        let cache = MetadataCache::new(dir.path().to_path_buf(), Duration::from_secs(60));
+
+        for _ in 0..2 {
+            let body = cache.get_text(reqwest::Client::new().get(&url)).await?;
+            assert_eq!(body, "{}");
+        }
+
+        assert_eq!(full_responses.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_revalidated() -> Result<()> {
+        let (url, full_responses) = start_server("{}").await?;
+        let dir = tempfile::tempdir()?;
+        let cache = MetadataCache::new(dir.path().to_path_buf(), Duration::ZERO);
+
+        for _ in 0..2 {
+            let body = cache.get_text(reqwest::Client::new().get(&url)).await?;
+            assert_eq!(body, "{}");
+        }
+
+        assert_eq!(full_responses.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_used_if_requests_fail() -> Result<()> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let url = format!(
+            "http://127.0.0.1:{}/versions.json",
+            listener.local_addr()?.port()
+        );
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0; 4096];
+            let _ = stream.read(&mut buf).await;
+            let _ = stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}")
+                .await;
+        });
+
+        let dir = tempfile::tempdir()?;
+        let cache = MetadataCache::new(dir.path().to_path_buf(), Duration::ZERO);
+        cache.get_text(reqwest::Client::new().get(&url)).await?;
+
+        // The listener is dropped with the server task, so the next request fails.
+        server.await?;
+        let body = cache.get_text(reqwest::Client::new().get(&url)).await?;
+        assert_eq!(body, "{}");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn parsed_version_urls_are_memoized() -> Result<()> {
+        let (url, _) = start_server("[]").await?;
+        let dir = tempfile::tempdir()?;
+        let cache = MetadataCache::new(dir.path().to_path_buf(), Duration::from_secs(60));
+        let parses = AtomicUsize::new(0);
+        let parse = |_: &str| {
+            parses.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![])
+        };
+
+        for _ in 0..2 {
+            let request = reqwest::Client::new().get(&url);
+            cache.get_version_urls(request, "linux64", parse).await?;
+        }
+        assert_eq!(parses.load(Ordering::SeqCst), 1);
+
+        let request = reqwest::Client::new().get(&url);
+        cache.get_version_urls(request, "win64", parse).await?;
+        assert_eq!(parses.load(Ordering::SeqCst), 2);
+
+        // Lists are kept with the cache, not the process.
+        let other = MetadataCache::new(dir.path().to_path_buf(), Duration::from_secs(60));
+        let request = reqwest::Client::new().get(&url);
+        other.get_version_urls(request, "linux64", parse).await?;
+        assert_eq!(parses.load(Ordering::SeqCst), 3);
+
+        let disabled = MetadataCache::disabled();
+        for _ in 0..2 {
+            let request = reqwest::Client::new().get(&url);
+            disabled.get_version_urls(request, "linux64", parse).await?;
+        }
+        assert_eq!(parses.load(Ordering::SeqCst), 5);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn cached_text_ignores_ttl() -> Result<()> {
+        let (url, _) = start_server("{}").await?;
+        let dir = tempfile::tempdir()?;
+        let cache = MetadataCache::new(dir.path().to_path_buf(), Duration::ZERO);
+
+        assert_eq!(cache.cached_text(&url), None);
+        cache.get_text(reqwest::Client::new().get(&url)).await?;
+        assert_eq!(cache.cached_text(&url).as_deref(), Some("{}"));
+        assert_eq!(MetadataCache::disabled().cached_text(&url), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn disabled_cache_always_downloads() -> Result<()> {
+        let (url, full_responses) = start_server("{}").await?;
+        let cache = MetadataCache::disabled();
+
+        for _ in 0..2 {
+            cache.get_text(reqwest::Client::new().get(&url)).await?;
+        }
+
+        assert_eq!(full_responses.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[cfg(target_family = "unix")]
 pub use unix_family::*;
@@ -6,13 +6,89 @@ pub use unix_family::*;
 pub use windows::*;
 
 use crate::os_specific::DefaultPathError;
+use crate::platform::{Os, Platform};
 
 #[cfg(target_family = "unix")]
 mod unix_family;
 #[cfg(target_os = "windows")]
 mod windows;
 
+/// Flag for writing chromedriver's log to a file.
+pub const LOG_FILE_FLAG: &str = "--log-path";
+
 pub fn default_driver_path() -> Result<PathBuf, DefaultPathError> {
+    default_driver_path_for(Os::current())
+}
+
+/// `HOME_DIR/bin` with the executable name of a driver for `os`, such as `chromedriver.exe` for Windows.
+pub fn default_driver_path_for(os: Os) -> Result<PathBuf, DefaultPathError> {
     let home_dir = home::home_dir().ok_or(DefaultPathError::HomeDir)?;
-    Ok(home_dir.join("bin").join(DRIVER_EXECUTABLE_NAME))
+    Ok(home_dir.join("bin").join(driver_executable_name(os)))
+}
+
+/// Chrome for Testing's default path if it is installed, otherwise Chrome's.
+pub fn default_browser_path() -> Result<PathBuf, DefaultPathError> {
+    let path_chrome_for_testing = super::chromedriver_for_testing::default_browser_path()?;
+    if path_chrome_for_testing.exists() {
+        Ok(path_chrome_for_testing)
+    } else {
+        super::chromedriver_old::default_browser_path()
+    }
+}
+
+/// Name of the chromedriver executable on `os`.
+pub fn driver_executable_name(os: Os) -> &'static str {
+    match os {
+        Os::Windows => "chromedriver.exe",
+        Os::Linux | Os::MacOs => "chromedriver",
+    }
+}
+
+/// Where distribution packages install chromedriver, such as Debian's `chromium-driver`.
+pub const DISTRO_DRIVER_PATHS: &[&str] = &[
+    "/usr/bin/chromedriver",
+    "/usr/lib/chromium/chromedriver",
+    "/usr/lib/chromium-browser/chromedriver",
+    "/snap/bin/chromium.chromedriver",
+];
+
+/// Alternative to upstream chromedriver for `platform`, which has no upstream build.
+/// On Linux, such as linux-aarch64, this points to the distribution's chromium driver package.
+pub fn unsupported_platform_hint(platform: Platform) -> Option<String> {
+    if platform.os != Os::Linux {
+        return None;
+    }
+
+    let installed = DISTRO_DRIVER_PATHS
+        .iter()
+        .map(Path::new)
+        .find(|path| platform == Platform::current() && path.exists());
+
+    Some(match installed {
+        Some(path) => format!(
+            "Use the distribution's chromedriver at {} instead",
+            path.display()
+        ),
+        None => "Install the distribution's chromium driver package instead, such as chromium-driver on Debian and Ubuntu".to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::platform::{Arch, Os, Platform};
+
+    use super::*;
+
+    #[test]
+    fn names_executables() {
+        assert_eq!(driver_executable_name(Os::Linux), "chromedriver");
+        assert_eq!(driver_executable_name(Os::MacOs), "chromedriver");
+        assert_eq!(driver_executable_name(Os::Windows), "chromedriver.exe");
+    }
+
+    #[test]
+    fn hints_only_on_linux() {
+        assert!(unsupported_platform_hint(Platform::new(Os::Linux, Arch::Aarch64)).is_some());
+        assert!(unsupported_platform_hint(Platform::new(Os::Windows, Arch::Aarch64)).is_none());
+    }
 }
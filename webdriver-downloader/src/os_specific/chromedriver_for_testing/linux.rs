@@ -4,8 +4,6 @@ use which::{which, Error};
 
 use crate::os_specific::DefaultPathError;
 
-pub const PLATFORM: &str = "linux64";
-
 pub const DRIVER_EXECUTABLE_NAME: &str = "chromedriver";
 
 pub const BROWSER_EXECUTABLE_NAMES: &[&str] =
@@ -11,3 +11,50 @@ mod linux;
 mod macos;
 #[cfg(target_os = "windows")]
 mod windows;
+
+use crate::platform::{Arch, Os, Platform};
+
+/// Platform name of Chrome for Testing downloads, such as `linux64` or `mac-arm64`.
+/// Returns `None` if there are no downloads for `platform`.
+pub fn platform_name(platform: Platform) -> Option<&'static str> {
+    match (platform.os, platform.arch) {
+        (Os::Linux, Arch::X86_64) => Some("linux64"),
+        (Os::MacOs, Arch::X86_64) => Some("mac-x64"),
+        (Os::MacOs, Arch::Aarch64) => Some("mac-arm64"),
+        (Os::Windows, Arch::X86) => Some("win32"),
+        (Os::Windows, Arch::X86_64) => Some("win64"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::platform::{Arch, Os, Platform};
+
+    use super::platform_name;
+
+    #[test]
+    fn names_platforms() {
+        let cases = [
+            (Os::Linux, Arch::X86_64, Some("linux64")),
+            (Os::Linux, Arch::X86, None),
+            (Os::Linux, Arch::Aarch64, None),
+            (Os::MacOs, Arch::X86_64, Some("mac-x64")),
+            (Os::MacOs, Arch::Aarch64, Some("mac-arm64")),
+            (Os::Windows, Arch::X86, Some("win32")),
+            (Os::Windows, Arch::X86_64, Some("win64")),
+            (Os::Windows, Arch::Aarch64, None),
+            (Os::Linux, Arch::Other("riscv64"), None),
+        ];
+
+        for (os, arch, expected) in cases {
+            assert_eq!(
+                platform_name(Platform::new(os, arch)),
+                expected,
+                "{:?} {:?}",
+                os,
+                arch
+            );
+        }
+    }
+}
@@ -4,8 +4,6 @@ use which::{which, Error};
 
 use crate::os_specific::DefaultPathError;
 
-pub const ZIPFILE_NAME_RE: &str = r#"<Key>([0-9.]*?)/chromedriver_linux64.zip</Key>"#;
-
 pub const BROWSER_EXECUTABLE_NAMES: &[&str] =
     &["google-chrome", "chrome", "chromium", "chromium-browser"];
 
@@ -24,10 +22,3 @@ pub fn default_browser_path() -> Result<PathBuf, DefaultPathError> {
 
     Err(DefaultPathError::BinaryNotFound)
 }
-
-pub fn build_url(version_string: &str) -> String {
-    format!(
-        "https://chromedriver.storage.googleapis.com/{}/chromedriver_linux64.zip",
-        version_string
-    )
-}
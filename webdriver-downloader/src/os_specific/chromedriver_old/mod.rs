@@ -11,3 +11,72 @@ mod linux;
 mod macos;
 #[cfg(target_os = "windows")]
 mod windows;
+
+use crate::platform::{Arch, Os, Platform};
+
+/// Platform name in old chromedriver zip files, such as `linux64` in `chromedriver_linux64.zip`.
+/// Returns `None` if there are no downloads for `platform`.
+pub fn platform_name(platform: Platform) -> Option<&'static str> {
+    match (platform.os, platform.arch) {
+        (Os::Linux, Arch::X86_64) => Some("linux64"),
+        (Os::MacOs, Arch::X86_64) => Some("mac64"),
+        (Os::MacOs, Arch::Aarch64) => Some("mac_arm64"),
+        // Only 32-bit builds were published for Windows, which also run on 64-bit Windows.
+        (Os::Windows, Arch::X86 | Arch::X86_64) => Some("win32"),
+        _ => None,
+    }
+}
+
+/// Regex for the keys of `platform_name`'s zip files in the bucket listing, capturing their version.
+pub fn zipfile_name_re(platform_name: &str) -> String {
+    format!(
+        r#"<Key>([0-9.]*?)/chromedriver_{}.zip</Key>"#,
+        regex::escape(platform_name)
+    )
+}
+
+pub fn build_url(version_string: &str, platform_name: &str) -> String {
+    format!(
+        "https://chromedriver.storage.googleapis.com/{}/chromedriver_{}.zip",
+        version_string, platform_name
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::platform::{Arch, Os, Platform};
+
+    use super::*;
+
+    #[test]
+    fn names_platforms() {
+        let cases = [
+            (Os::Linux, Arch::X86_64, Some("linux64")),
+            (Os::Linux, Arch::X86, None),
+            (Os::Linux, Arch::Aarch64, None),
+            (Os::MacOs, Arch::X86_64, Some("mac64")),
+            (Os::MacOs, Arch::Aarch64, Some("mac_arm64")),
+            (Os::Windows, Arch::X86, Some("win32")),
+            (Os::Windows, Arch::X86_64, Some("win32")),
+            (Os::Linux, Arch::Other("riscv64"), None),
+        ];
+
+        for (os, arch, expected) in cases {
+            assert_eq!(
+                platform_name(Platform::new(os, arch)),
+                expected,
+                "{:?} {:?}",
+                os,
+                arch
+            );
+        }
+    }
+
+    #[test]
+    fn builds_urls() {
+        assert_eq!(
+            build_url("114.0.5735.90", "mac64"),
+            "https://chromedriver.storage.googleapis.com/114.0.5735.90/chromedriver_mac64.zip"
+        );
+    }
+}
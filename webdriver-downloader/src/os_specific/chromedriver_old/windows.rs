@@ -2,8 +2,6 @@ use std::path::PathBuf;
 
 use crate::os_specific::DefaultPathError;
 
-pub const ZIPFILE_NAME_RE: &str = r#"<Key>([0-9.]*?)/chromedriver_win32.zip</Key>"#;
-
 pub fn default_browser_path() -> Result<PathBuf, DefaultPathError> {
     let program_files = std::env::var("ProgramFiles")?;
     Ok(PathBuf::from(format!(
@@ -11,10 +9,3 @@ pub fn default_browser_path() -> Result<PathBuf, DefaultPathError> {
         program_files
     )))
 }
-
-pub fn build_url(version_string: &str) -> String {
-    format!(
-        "https://chromedriver.storage.googleapis.com/{}/chromedriver_win32.zip",
-        version_string
-    )
-}
@@ -21,8 +21,87 @@ mod unix_family;
 use std::path::PathBuf;
 
 use crate::os_specific::DefaultPathError;
+use crate::platform::{Arch, Os, Platform};
+
+/// Suffix of geckodriver release assets for `platform`, such as `linux64.tar.gz`.
+/// Returns `None` if there are no releases for `platform`.
+pub fn asset_suffix(platform: Platform) -> Option<&'static str> {
+    match (platform.os, platform.arch) {
+        (Os::Linux, Arch::X86) => Some("linux32.tar.gz"),
+        (Os::Linux, Arch::X86_64) => Some("linux64.tar.gz"),
+        (Os::Linux, Arch::Aarch64) => Some("linux-aarch64.tar.gz"),
+        (Os::MacOs, Arch::X86_64) => Some("macos.tar.gz"),
+        (Os::MacOs, Arch::Aarch64) => Some("macos-aarch64.tar.gz"),
+        (Os::Windows, Arch::X86) => Some("win32.zip"),
+        (Os::Windows, Arch::X86_64) => Some("win64.zip"),
+        _ => None,
+    }
+}
+
+/// Name of the release asset for `tag`, such as `geckodriver-v0.34.0-linux64.tar.gz` for `v0.34.0` and `linux64.tar.gz`.
+pub fn asset_name(tag: &str, asset_suffix: &str) -> String {
+    format!("geckodriver-{}-{}", tag, asset_suffix)
+}
+
+/// Name of the geckodriver executable on `os`.
+pub fn driver_executable_name(os: Os) -> &'static str {
+    match os {
+        Os::Windows => "geckodriver.exe",
+        Os::Linux | Os::MacOs => "geckodriver",
+    }
+}
 
 pub fn default_driver_path() -> Result<PathBuf, DefaultPathError> {
+    default_driver_path_for(Os::current())
+}
+
+/// `HOME_DIR/bin` with the executable name of a driver for `os`, such as `geckodriver.exe` for Windows.
+pub fn default_driver_path_for(os: Os) -> Result<PathBuf, DefaultPathError> {
     let home_dir = home::home_dir().ok_or(DefaultPathError::HomeDir)?;
-    Ok(home_dir.join("bin").join(DRIVER_EXECUTABLE_NAME))
+    Ok(home_dir.join("bin").join(driver_executable_name(os)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::platform::{Arch, Os, Platform};
+
+    use super::*;
+
+    #[test]
+    fn names_assets() {
+        let cases = [
+            (Os::Linux, Arch::X86, Some("linux32.tar.gz")),
+            (Os::Linux, Arch::X86_64, Some("linux64.tar.gz")),
+            (Os::Linux, Arch::Aarch64, Some("linux-aarch64.tar.gz")),
+            (Os::MacOs, Arch::X86, None),
+            (Os::MacOs, Arch::X86_64, Some("macos.tar.gz")),
+            (Os::MacOs, Arch::Aarch64, Some("macos-aarch64.tar.gz")),
+            (Os::Windows, Arch::X86, Some("win32.zip")),
+            (Os::Windows, Arch::X86_64, Some("win64.zip")),
+            (Os::Windows, Arch::Aarch64, None),
+            (Os::Linux, Arch::Other("riscv64"), None),
+        ];
+
+        for (os, arch, expected) in cases {
+            assert_eq!(
+                asset_suffix(Platform::new(os, arch)),
+                expected,
+                "{:?} {:?}",
+                os,
+                arch
+            );
+        }
+
+        assert_eq!(
+            asset_name("v0.34.0", "win64.zip"),
+            "geckodriver-v0.34.0-win64.zip"
+        );
+    }
+
+    #[test]
+    fn names_executables() {
+        assert_eq!(driver_executable_name(Os::Linux), "geckodriver");
+        assert_eq!(driver_executable_name(Os::MacOs), "geckodriver");
+        assert_eq!(driver_executable_name(Os::Windows), "geckodriver.exe");
+    }
 }
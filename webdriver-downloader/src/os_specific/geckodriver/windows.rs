@@ -16,13 +16,6 @@ pub fn default_browser_path() -> Result<PathBuf, DefaultPathError> {
     )))
 }
 
-pub fn build_url(version_string: &str) -> String {
-    format!(
-        "https://github.com/mozilla/geckodriver/releases/download/v{ver}/geckodriver-v{ver}-win64.zip",
-        ver=version_string
-    )
-}
-
 #[tracing::instrument]
 pub fn binary_version(browser_path: &Path) -> Result<Version, VersionReqError> {
     let mut child = std::process::Command::new("powershell");
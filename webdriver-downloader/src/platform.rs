@@ -0,0 +1,209 @@
+//! Target platform of the downloaded drivers.
+//!
+//! Driver infos download for [`Platform::current`] by default, but can download drivers for
+//! another platform, for example to build a Windows image on a Linux host.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ParsePlatformError {
+    #[error("Expected a platform like \"linux-x86_64\", got \"{0}\"")]
+    Format(String),
+    #[error("Unknown operating system \"{0}\"")]
+    Os(String),
+    #[error("Unknown architecture \"{0}\"")]
+    Arch(String),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Os {
+    Linux,
+    MacOs,
+    Windows,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Arch {
+    X86,
+    X86_64,
+    Aarch64,
+    /// Architecture without driver builds, such as `arm` or `riscv64`, named as in [`std::env::consts::ARCH`].
+    /// Only returned by [`Arch::current`].
+    Other(&'static str),
+}
+
+/// Operating system and architecture a driver is downloaded for.
+///
+/// Parses from and displays as `os-arch`, such as `linux-x86_64` or `macos-aarch64`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Platform {
+    pub os: Os,
+    pub arch: Arch,
+}
+
+impl Os {
+    /// Operating system this crate was compiled for.
+    pub fn current() -> Self {
+        if cfg!(target_os = "windows") {
+            Os::Windows
+        } else if cfg!(target_os = "macos") {
+            Os::MacOs
+        } else {
+            Os::Linux
+        }
+    }
+}
+
+impl Arch {
+    /// Architecture this crate was compiled for.
+    pub fn current() -> Self {
+        Self::from_target_arch(std::env::consts::ARCH)
+    }
+
+    fn from_target_arch(target_arch: &'static str) -> Self {
+        match target_arch {
+            "x86" => Arch::X86,
+            "x86_64" => Arch::X86_64,
+            "aarch64" => Arch::Aarch64,
+            other => Arch::Other(other),
+        }
+    }
+}
+
+impl Platform {
+    pub fn new(os: Os, arch: Arch) -> Self {
+        Platform { os, arch }
+    }
+
+    /// Platform this crate was compiled for.
+    pub fn current() -> Self {
+        Platform::new(Os::current(), Arch::current())
+    }
+}
+
+impl Default for Platform {
+    /// [`Platform::current`].
+    fn default() -> Self {
+        Platform::current()
+    }
+}
+
+impl Display for Os {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Os::Linux => "linux",
+            Os::MacOs => "macos",
+            Os::Windows => "windows",
+        })
+    }
+}
+
+impl Display for Arch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Arch::X86 => "x86",
+            Arch::X86_64 => "x86_64",
+            Arch::Aarch64 => "aarch64",
+            Arch::Other(name) => name,
+        })
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.os, self.arch)
+    }
+}
+
+impl FromStr for Os {
+    type Err = ParsePlatformError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "linux" => Ok(Os::Linux),
+            "macos" | "mac" | "darwin" => Ok(Os::MacOs),
+            "windows" | "win" => Ok(Os::Windows),
+            _ => Err(ParsePlatformError::Os(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for Arch {
+    type Err = ParsePlatformError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "x86" | "i686" | "x32" => Ok(Arch::X86),
+            "x86_64" | "x64" | "amd64" => Ok(Arch::X86_64),
+            "aarch64" | "arm64" => Ok(Arch::Aarch64),
+            _ => Err(ParsePlatformError::Arch(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for Platform {
+    type Err = ParsePlatformError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (os, arch) = s
+            .split_once('-')
+            .ok_or_else(|| ParsePlatformError::Format(s.to_string()))?;
+
+        Ok(Platform::new(os.parse()?, arch.parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Arch, Os, ParsePlatformError, Platform};
+
+    #[test]
+    fn parses_platforms() {
+        assert_eq!(
+            "linux-x86_64".parse::<Platform>().unwrap(),
+            Platform::new(Os::Linux, Arch::X86_64)
+        );
+        assert_eq!(
+            "mac-arm64".parse::<Platform>().unwrap(),
+            Platform::new(Os::MacOs, Arch::Aarch64)
+        );
+        assert_eq!(
+            "Windows-x86".parse::<Platform>().unwrap(),
+            Platform::new(Os::Windows, Arch::X86)
+        );
+
+        assert!(matches!(
+            "linux".parse::<Platform>(),
+            Err(ParsePlatformError::Format(_))
+        ));
+        assert!(matches!(
+            "beos-x86_64".parse::<Platform>(),
+            Err(ParsePlatformError::Os(_))
+        ));
+        assert!(matches!(
+            "linux-riscv64".parse::<Platform>(),
+            Err(ParsePlatformError::Arch(_))
+        ));
+    }
+
+    #[test]
+    fn keeps_unsupported_architectures() {
+        assert_eq!(Arch::from_target_arch("x86_64"), Arch::X86_64);
+        assert_eq!(Arch::from_target_arch("riscv64"), Arch::Other("riscv64"));
+        assert_eq!(
+            Platform::new(Os::Linux, Arch::from_target_arch("arm")).to_string(),
+            "linux-arm"
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for os in [Os::Linux, Os::MacOs, Os::Windows] {
+            for arch in [Arch::X86, Arch::X86_64, Arch::Aarch64] {
+                let platform = Platform::new(os, arch);
+                assert_eq!(platform.to_string().parse::<Platform>().unwrap(), platform);
+            }
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Installs drivers into `target/webdriver` from `build.rs` or test setup code.
+//!
+//! Requires the `provision` feature.
+//!
+//! ```no_run
+//! // In `main` of build.rs, with webdriver-downloader in [build-dependencies].
+//! webdriver_downloader::provision::cargo_build_script("chrome").unwrap();
+//! ```
+//!
+//! The crate can then read the driver path with `env!("WEBDRIVER_CHROMEDRIVER_PATH")`.
+//!
+//! Version metadata is cached like any other download, in [`MetadataCache::default_dir`](crate::metadata_cache::MetadataCache::default_dir).
+//!
+//! If [`OFFLINE_ENV`] or `CARGO_NET_OFFLINE` is `true`, nothing is downloaded. The installed driver is used
+//! if it passes [`verify_driver_offline`](crate::traits::offline_verification_info::OfflineVerificationInfo::verify_driver_offline),
+//! which reads version metadata from the cache or the snapshot bundled with the crate.
+//! Cargo does not export `CARGO_NET_OFFLINE` to build scripts for `--offline` or `net.offline`,
+//! so only an explicitly set environment variable counts.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tokio::runtime::Builder;
+
+use crate::os_specific::DefaultPathError;
+use crate::platform::Platform;
+use crate::registry::{DriverOptions, DriverRegistry, RegisteredDriver};
+use crate::traits::offline_verification_info::OfflineVerificationError;
+use crate::traits::webdriver_download_info::WebdriverDownloadError;
+
+/// Environment variable that disables downloads when set to `true` or `1`.
+pub const OFFLINE_ENV: &str = "WEBDRIVER_DOWNLOADER_OFFLINE";
+
+/// Cargo's offline setting, when set as an environment variable. `--offline` does not set it.
+const CARGO_OFFLINE_ENV: &str = "CARGO_NET_OFFLINE";
+
+/// Number of versions tried before giving up.
+const MAX_TRIES: usize = 5;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ProvisionError {
+    #[error("Unknown driver type \"{0}\"")]
+    UnknownDriverType(String),
+    #[error(transparent)]
+    DefaultPath(#[from] DefaultPathError),
+    #[error("Failed to create directory {0}")]
+    CreateDir(PathBuf, #[source] io::Error),
+    #[error("Failed to create runtime")]
+    Runtime(#[source] io::Error),
+    #[error(
+        "{0} is not installed, and downloads are disabled by {OFFLINE_ENV} or {CARGO_OFFLINE_ENV}"
+    )]
+    Offline(PathBuf),
+    #[error("{0} is installed, but downloads are disabled and it failed offline verification")]
+    OfflineVerification(PathBuf, #[source] Box<OfflineVerificationError>),
+    #[error(transparent)]
+    Download(#[from] WebdriverDownloadError),
+}
+
+/// For `build.rs`. Runs [`ensure_driver`] and exports the driver path to the crate being built as [`path_env_var`].
+pub fn cargo_build_script(driver_type: &str) -> Result<PathBuf, ProvisionError> {
+    println!("cargo:rerun-if-env-changed={}", OFFLINE_ENV);
+    println!("cargo:rerun-if-env-changed={}", CARGO_OFFLINE_ENV);
+
+    let driver_path = ensure_driver(driver_type)?;
+
+    println!("cargo:rerun-if-changed={}", driver_path.display());
+    println!(
+        "cargo:rustc-env={}={}",
+        path_env_var(&driver_path),
+        driver_path.display()
+    );
+
+    Ok(driver_path)
+}
+
+/// Installs the driver registered as `driver_type` in [`DriverRegistry::default`] into [`webdriver_dir`],
+/// unless it is already installed, and returns the driver path.
+///
+/// The environment of the current process is left alone, since changing it races with other threads,
+/// such as other tests. Pass the path on explicitly, as [`cargo_build_script`] does.
+pub fn ensure_driver(driver_type: &str) -> Result<PathBuf, ProvisionError> {
+    ensure_driver_in(&DriverRegistry::default(), driver_type)
+}
+
+/// [`ensure_driver`] with drivers from `registry`.
+pub fn ensure_driver_in(
+    registry: &DriverRegistry,
+    driver_type: &str,
+) -> Result<PathBuf, ProvisionError> {
+    install_into(registry, driver_type, &webdriver_dir(), is_offline())
+}
+
+/// `webdriver` in the cargo target directory.
+///
+/// The target directory is `CARGO_TARGET_DIR` if set, otherwise the closest directory containing cargo's
+/// `CACHEDIR.TAG` above the build script output or the running executable, falling back to `target`
+/// in `CARGO_MANIFEST_DIR`.
+pub fn webdriver_dir() -> PathBuf {
+    let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .or_else(|| {
+            let out_dir = std::env::var_os("OUT_DIR").map(PathBuf::from);
+            let current_exe = std::env::current_exe().ok();
+
+            out_dir
+                .into_iter()
+                .chain(current_exe)
+                .find_map(|path| find_target_dir(&path))
+        })
+        .or_else(|| {
+            std::env::var_os("CARGO_MANIFEST_DIR").map(|dir| Path::new(&dir).join("target"))
+        })
+        .unwrap_or_else(|| PathBuf::from("target"));
+
+    target_dir.join("webdriver")
+}
+
+/// Environment variable the driver path is exported as, such as `WEBDRIVER_CHROMEDRIVER_PATH`.
+pub fn path_env_var(driver_path: &Path) -> String {
+    let name = driver_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    let name: String = name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_uppercase(),
+            false => '_',
+        })
+        .collect();
+
+    format!("WEBDRIVER_{}_PATH", name)
+}
+
+fn is_offline() -> bool {
+    [OFFLINE_ENV, CARGO_OFFLINE_ENV].iter().any(|name| {
+        std::env::var(name).is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+    })
+}
+
+fn find_target_dir(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| dir.join("CACHEDIR.TAG").is_file())
+        .map(Path::to_path_buf)
+}
+
+fn install_into(
+    registry: &DriverRegistry,
+    driver_type: &str,
+    dir: &Path,
+    offline: bool,
+) -> Result<PathBuf, ProvisionError> {
+    let factory = registry
+        .get(driver_type)
+        .ok_or_else(|| ProvisionError::UnknownDriverType(driver_type.to_string()))?;
+
+    let default_driver_path = factory.default_driver_path(Platform::current())?;
+    let executable_name = default_driver_path
+        .file_name()
+        .unwrap_or(default_driver_path.as_os_str());
+    let driver_path = dir.join(executable_name);
+
+    if offline && !driver_path.exists() {
+        return Err(ProvisionError::Offline(driver_path));
+    }
+
+    let driver_info = factory.build(DriverOptions::new(
+        driver_path.clone(),
+        factory.default_browser_path()?,
+    ));
+
+    if offline {
+        verify_installed_offline(driver_info.as_ref())?;
+        return Ok(driver_path);
+    }
+
+    std::fs::create_dir_all(dir).map_err(|e| ProvisionError::CreateDir(dir.to_path_buf(), e))?;
+    let runtime = Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(ProvisionError::Runtime)?;
+
+    if !runtime.block_on(is_installed(driver_info.as_ref())) {
+        runtime.block_on(driver_info.download_verify_install(MAX_TRIES))?;
+    }
+
+    Ok(driver_path)
+}
+
+/// Runs offline verification on an installed driver, where the driver supports it.
+fn verify_installed_offline(driver_info: &dyn RegisteredDriver) -> Result<(), ProvisionError> {
+    match driver_info.as_offline() {
+        Some(offline) => offline.verify_driver_offline().map_err(|e| {
+            ProvisionError::OfflineVerification(
+                driver_info.driver_install_path().to_path_buf(),
+                Box::new(e),
+            )
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Checks versions offline where the driver supports it, so that up to date drivers are not started on every build.
+async fn is_installed(driver_info: &dyn RegisteredDriver) -> bool {
+    match driver_info.as_offline() {
+        Some(offline) => {
+            driver_info.driver_install_path().exists() && offline.verify_driver_offline().is_ok()
+        }
+        None => driver_info.is_installed().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::driver_impls::geckodriver_info::GeckodriverInfo;
+    use crate::platform::Platform;
+    use crate::registry::DriverRegistry;
+
+    use super::{
+        find_target_dir, install_into, path_env_var, verify_installed_offline, ProvisionError,
+    };
+
+    #[test]
+    fn names_env_var_after_driver() {
+        assert_eq!(
+            path_env_var(Path::new("target/webdriver/chromedriver.exe")),
+            "WEBDRIVER_CHROMEDRIVER_PATH"
+        );
+        assert_eq!(
+            path_env_var(Path::new("geckodriver")),
+            "WEBDRIVER_GECKODRIVER_PATH"
+        );
+        assert_eq!(
+            path_env_var(Path::new("custom-driver")),
+            "WEBDRIVER_CUSTOM_DRIVER_PATH"
+        );
+    }
+
+    #[test]
+    fn finds_target_dir_above_path() {
+        let target_dir = tempfile::tempdir().unwrap();
+        std::fs::write(target_dir.path().join("CACHEDIR.TAG"), "").unwrap();
+        let out_dir = target_dir.path().join("debug/build/some-crate-0123/out");
+
+        assert_eq!(find_target_dir(&out_dir).unwrap(), target_dir.path());
+        assert_eq!(find_target_dir(Path::new("/")), None);
+    }
+
+    #[test]
+    fn offline_uses_installed_driver_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = DriverRegistry::default();
+
+        assert!(matches!(
+            install_into(&registry, "gecko", dir.path(), true),
+            Err(ProvisionError::Offline(_))
+        ));
+
+        // An empty file has no version, so it fails offline verification.
+        let installed = dir.path().join(path_with_default_name(&registry, "gecko"));
+        std::fs::write(&installed, "").unwrap();
+        assert!(install_into(&registry, "gecko", dir.path(), true).is_err());
+
+        assert!(matches!(
+            install_into(&registry, "missing", dir.path(), true),
+            Err(ProvisionError::UnknownDriverType(_))
+        ));
+    }
+
+    #[test]
+    fn offline_verifies_installed_driver() {
+        let dir = tempfile::tempdir().unwrap();
+        let installed = dir.path().join("geckodriver");
+        std::fs::write(&installed, "").unwrap();
+
+        let driver_info = GeckodriverInfo::builder()
+            .driver_install_path(&installed)
+            .browser_path(dir.path().join("firefox"))
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            verify_installed_offline(&driver_info),
+            Err(ProvisionError::OfflineVerification(path, _)) if path == installed
+        ));
+    }
+
+    fn path_with_default_name(registry: &DriverRegistry, driver_type: &str) -> String {
+        let default_driver_path = registry
+            .get(driver_type)
+            .unwrap()
+            .default_driver_path(Platform::current())
+            .unwrap();
+
+        default_driver_path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned()
+    }
+}
@@ -0,0 +1,220 @@
+//! Drivers looked up by name, such as `chrome` or `gecko`.
+//!
+//! [`DriverRegistry::default`] contains the drivers provided by this library.
+//! Other crates can [`register`](DriverRegistry::register) their own [`WebdriverDownloadInfo`] implementations
+//! by implementing [`DriverFactory`] and [`RegisteredDriver`].
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::driver_impls::chromedriver_info::ChromedriverInfo;
+use crate::driver_impls::geckodriver_info::GeckodriverInfo;
+use crate::os_specific;
+use crate::os_specific::DefaultPathError;
+use crate::platform::Platform;
+use crate::traits::offline_verification_info::OfflineVerificationInfo;
+use crate::traits::verification_info::BrowserOptions;
+use crate::traits::verification_scenario::{default_scenarios, VerificationScenario};
+use crate::traits::webdriver_download_info::WebdriverDownloadInfo;
+
+/// Names of the drivers in [`DriverRegistry::default`].
+pub const BUILTIN_DRIVERS: [&str; 2] = ["chrome", "gecko"];
+
+/// Driver built by a [`DriverFactory`].
+pub trait RegisteredDriver: WebdriverDownloadInfo {
+    /// The driver as an [`OfflineVerificationInfo`], if it can be checked without network access.
+    /// `None` by default.
+    fn as_offline(&self) -> Option<&dyn OfflineVerificationInfo> {
+        None
+    }
+}
+
+/// Settings a [`DriverFactory`] builds a driver with.
+#[derive(Debug, Clone)]
+pub struct DriverOptions {
+    pub driver_install_path: PathBuf,
+    pub browser_path: PathBuf,
+    pub verification_scenarios: Vec<Arc<dyn VerificationScenario>>,
+    pub browser_options: BrowserOptions,
+    pub platform: Platform,
+}
+
+impl DriverOptions {
+    /// Options with the default scenarios, browser options and the current platform.
+    pub fn new(driver_install_path: PathBuf, browser_path: PathBuf) -> Self {
+        DriverOptions {
+            driver_install_path,
+            browser_path,
+            verification_scenarios: default_scenarios(),
+            browser_options: BrowserOptions::default(),
+            platform: Platform::current(),
+        }
+    }
+}
+
+/// Constructs a driver and knows its default paths.
+pub trait DriverFactory: Debug + Send + Sync {
+    /// Path to install a driver for `platform` to if none is given.
+    fn default_driver_path(&self, platform: Platform) -> Result<PathBuf, DefaultPathError>;
+
+    /// Path to the browser if none is given.
+    fn default_browser_path(&self) -> Result<PathBuf, DefaultPathError>;
+
+    fn build(&self, options: DriverOptions) -> Box<dyn RegisteredDriver>;
+}
+
+/// Builds [`ChromedriverInfo`]. Prefers Chrome for Testing if it is installed.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ChromedriverFactory;
+
+impl DriverFactory for ChromedriverFactory {
+    fn default_driver_path(&self, platform: Platform) -> Result<PathBuf, DefaultPathError> {
+        os_specific::chromedriver::default_driver_path_for(platform.os)
+    }
+
+    fn default_browser_path(&self) -> Result<PathBuf, DefaultPathError> {
+        os_specific::chromedriver::default_browser_path()
+    }
+
+    fn build(&self, options: DriverOptions) -> Box<dyn RegisteredDriver> {
+        let mut driver_info =
+            ChromedriverInfo::new(options.driver_install_path, options.browser_path);
+        driver_info.set_verification_scenarios(options.verification_scenarios);
+        driver_info.set_browser_options(options.browser_options);
+        driver_info.set_platform(options.platform);
+
+        Box::new(driver_info)
+    }
+}
+
+/// Builds [`GeckodriverInfo`].
+#[derive(Debug, Copy, Clone, Default)]
+pub struct GeckodriverFactory;
+
+impl DriverFactory for GeckodriverFactory {
+    fn default_driver_path(&self, platform: Platform) -> Result<PathBuf, DefaultPathError> {
+        os_specific::geckodriver::default_driver_path_for(platform.os)
+    }
+
+    fn default_browser_path(&self) -> Result<PathBuf, DefaultPathError> {
+        os_specific::geckodriver::default_browser_path()
+    }
+
+    fn build(&self, options: DriverOptions) -> Box<dyn RegisteredDriver> {
+        let mut driver_info =
+            GeckodriverInfo::new(options.driver_install_path, options.browser_path);
+        driver_info.verification_scenarios = options.verification_scenarios;
+        driver_info.browser_options = options.browser_options;
+        driver_info.platform = options.platform;
+
+        Box::new(driver_info)
+    }
+}
+
+/// [`DriverFactory`]s by name.
+#[derive(Debug, Clone)]
+pub struct DriverRegistry {
+    factories: BTreeMap<String, Arc<dyn DriverFactory>>,
+}
+
+impl DriverRegistry {
+    /// Registry without any drivers.
+    pub fn empty() -> Self {
+        DriverRegistry {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `factory` as `name`. Returns the factory previously registered as `name`, if any.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl DriverFactory + 'static,
+    ) -> Option<Arc<dyn DriverFactory>> {
+        self.factories.insert(name.into(), Arc::new(factory))
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn DriverFactory>> {
+        self.factories.get(name).cloned()
+    }
+
+    /// Registered names in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+}
+
+impl Default for DriverRegistry {
+    /// Registry with [`BUILTIN_DRIVERS`].
+    fn default() -> Self {
+        let mut registry = DriverRegistry::empty();
+        registry.register("chrome", ChromedriverFactory);
+        registry.register("gecko", GeckodriverFactory);
+
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::os_specific::DefaultPathError;
+    use crate::platform::Platform;
+
+    use super::{
+        DriverFactory, DriverOptions, DriverRegistry, GeckodriverFactory, RegisteredDriver,
+        BUILTIN_DRIVERS,
+    };
+
+    #[derive(Debug)]
+    struct FixedPathFactory;
+
+    impl DriverFactory for FixedPathFactory {
+        fn default_driver_path(&self, _platform: Platform) -> Result<PathBuf, DefaultPathError> {
+            Ok(PathBuf::from("/opt/drivers/custom"))
+        }
+
+        fn default_browser_path(&self) -> Result<PathBuf, DefaultPathError> {
+            Ok(PathBuf::from("/opt/custom-browser"))
+        }
+
+        fn build(&self, options: DriverOptions) -> Box<dyn RegisteredDriver> {
+            GeckodriverFactory.build(options)
+        }
+    }
+
+    #[test]
+    fn default_contains_builtin_drivers() {
+        let registry = DriverRegistry::default();
+
+        assert_eq!(registry.names().collect::<Vec<_>>(), BUILTIN_DRIVERS);
+    }
+
+    #[test]
+    fn registers_custom_driver() {
+        let mut registry = DriverRegistry::default();
+        assert!(registry.register("custom", FixedPathFactory).is_none());
+
+        let factory = registry.get("custom").unwrap();
+        assert_eq!(
+            factory.default_driver_path(Platform::current()).unwrap(),
+            PathBuf::from("/opt/drivers/custom")
+        );
+
+        let driver = factory.build(DriverOptions::new(
+            factory.default_driver_path(Platform::current()).unwrap(),
+            factory.default_browser_path().unwrap(),
+        ));
+        assert_eq!(
+            driver.driver_install_path(),
+            PathBuf::from("/opt/drivers/custom")
+        );
+        assert!(driver.as_offline().is_some());
+
+        assert!(registry.register("custom", FixedPathFactory).is_some());
+        assert!(registry.get("missing").is_none());
+    }
+}
@@ -1,17 +1,31 @@
 use std::ffi::OsStr;
-use std::fmt::Debug;
 use std::fs;
 use std::fs::File;
-use std::io::{self, Cursor};
+use std::io::{self, Cursor, Read};
 use std::path::{Path, PathBuf};
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use reqwest::IntoUrl;
+use reqwest::header::CONTENT_TYPE;
 use tar::Archive;
 use tempfile::TempDir;
 use zip::ZipArchive;
 
+use crate::executable_format::ExecutableFormat;
+use crate::platform::Platform;
+
+/// Maximum number of entries in a driver archive. Driver archives have a handful.
+pub const MAX_ARCHIVE_ENTRIES: usize = 1000;
+
+/// Maximum decompressed size of the driver executable.
+pub const MAX_DRIVER_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Maximum size of a downloaded driver archive.
+pub const MAX_DOWNLOAD_SIZE: u64 = 512 * 1024 * 1024;
+
+/// Maximum decompressed size of a whole tarball, including the entries that are skipped.
+pub const MAX_TARBALL_SIZE: u64 = 1024 * 1024 * 1024;
+
 /// Error that can occur during installation.
 #[derive(thiserror::Error, Debug)]
 pub enum InstallationError {
@@ -19,12 +33,38 @@ pub enum InstallationError {
     Download(#[from] reqwest::Error),
     #[error("Unknown archive format.")]
     UnknownArchiveFormat,
+    #[error("Extracting {0} archives requires the \"{0}\" feature")]
+    ArchiveFeatureDisabled(&'static str),
+    #[error("Download is larger than {0} bytes")]
+    DownloadTooLarge(u64),
     #[error("Failed to extract driver zipfile: {0}")]
     ExtractZip(#[from] zip::result::ZipError),
     #[error("Failed to extract driver tarball: {0}")]
     ExtractTar(io::Error),
+    #[error("Archive has more than {0} entries")]
+    TooManyArchiveEntries(usize),
+    #[error("Driver is larger than {0} bytes")]
+    DriverTooLarge(u64),
+    #[error("Decompressed tarball is larger than {0} bytes")]
+    TarballTooLarge(u64),
+    #[error("Driver entry \"{0}\" in archive is a link")]
+    DriverIsLink(String),
+    #[error("Driver executable \"{name}\" not found in archive. Entries: {}", .entries.join(", "))]
+    ExecutableNotFoundInArchive { name: String, entries: Vec<String> },
     #[error("Failed to write driver to disk: {0}")]
     Write(io::Error),
+    #[error("Failed to read extracted driver: {0}")]
+    Read(io::Error),
+    /// The extracted driver is not an executable for the target platform, such as a wrong architecture or a corrupted download.
+    /// `found` is `None` if it is not an ELF, Mach-O or PE executable.
+    #[error(
+        "Extracted driver cannot run on {platform}, it is {}",
+        .found.as_deref().unwrap_or("not an executable")
+    )]
+    IncompatibleExecutable {
+        platform: Platform,
+        found: Option<String>,
+    },
     #[error(transparent)]
     AddExecutePermission(#[from] AddExecutePermissionError),
     #[error(transparent)]
@@ -40,31 +80,68 @@ pub trait WebdriverInstallationInfo {
     /// Driver executable name.
     fn driver_executable_name(&self) -> &str;
 
+    /// Platform the driver must run on. Downloaded drivers for other platforms are rejected.
+    /// Defaults to [`Platform::current`].
+    fn target_platform(&self) -> Platform {
+        Platform::current()
+    }
+
     /// Downloads url and extracts the driver executable to tempdir.
     #[tracing::instrument(skip(self))]
-    async fn download_in_tempdir<U: IntoUrl + AsRef<str> + Debug + Send>(
+    async fn download_in_tempdir(
         &self,
-        url: U,
+        url: &str,
         dir: &TempDir,
     ) -> Result<PathBuf, InstallationError> {
-        let archive_type =
-            detect_archive_type(url.as_ref()).ok_or(InstallationError::UnknownArchiveFormat)?;
+        let response = reqwest::get(url).await?.error_for_status()?;
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let bytes = read_body(response, MAX_DOWNLOAD_SIZE).await?;
 
-        let response = reqwest::get(url).await?;
-        let content = Cursor::new(response.bytes().await?);
+        let archive_type = detect_archive_type(&bytes, content_type.as_deref())
+            .ok_or(InstallationError::UnknownArchiveFormat)?;
 
         let driver_executable_name = self.driver_executable_name();
         let driver_path = dir.path().join(driver_executable_name);
 
+        let limits = ExtractionLimits::default();
         match archive_type {
             ArchiveType::Zip => {
-                extract_zip(content, driver_executable_name, &driver_path)?;
+                extract_zip(
+                    Cursor::new(bytes),
+                    driver_executable_name,
+                    &driver_path,
+                    limits,
+                )?;
             }
             ArchiveType::TarGz => {
-                extract_tarball(content, driver_executable_name, &driver_path)?;
+                let tar = flate2::bufread::GzDecoder::new(bytes.as_ref());
+                extract_tarball(tar, driver_executable_name, &driver_path, limits)?;
+            }
+            #[cfg(feature = "xz")]
+            ArchiveType::TarXz => {
+                let tar = xz2::bufread::XzDecoder::new(bytes.as_ref());
+                extract_tarball(tar, driver_executable_name, &driver_path, limits)?;
+            }
+            #[cfg(not(feature = "xz"))]
+            ArchiveType::TarXz => return Err(InstallationError::ArchiveFeatureDisabled("xz")),
+            #[cfg(feature = "bzip2")]
+            ArchiveType::TarBz2 => {
+                let tar = bzip2::bufread::BzDecoder::new(bytes.as_ref());
+                extract_tarball(tar, driver_executable_name, &driver_path, limits)?;
+            }
+            #[cfg(not(feature = "bzip2"))]
+            ArchiveType::TarBz2 => return Err(InstallationError::ArchiveFeatureDisabled("bzip2")),
+            ArchiveType::Executable => {
+                copy_driver(bytes.as_ref(), &driver_path, limits)?;
             }
         }
 
+        check_executable_format(&driver_path, self.target_platform())?;
+
         #[cfg(unix)]
         add_execute_permission(&driver_path)?;
 
@@ -73,10 +150,7 @@ pub trait WebdriverInstallationInfo {
 
     /// installs driver from `temp_dir_path` to [`self.driver_install_path()`](Self::driver_install_path).
     #[tracing::instrument(skip(self))]
-    fn install_driver<P: AsRef<Path> + Debug>(
-        &self,
-        temp_driver_path: &P,
-    ) -> Result<(), InstallationError> {
+    fn install_driver(&self, temp_driver_path: &Path) -> Result<(), InstallationError> {
         fs::rename(temp_driver_path, self.driver_install_path())
             .or_else(|e| {
                 // io::ErrorKind::CrossesDevices => try to copy instead
@@ -91,70 +165,253 @@ pub trait WebdriverInstallationInfo {
     }
 }
 
+/// Reads the body of `response`, failing once it is larger than `limit` bytes.
+async fn read_body(
+    mut response: reqwest::Response,
+    limit: u64,
+) -> Result<Bytes, InstallationError> {
+    if response
+        .content_length()
+        .is_some_and(|length| length > limit)
+    {
+        return Err(InstallationError::DownloadTooLarge(limit));
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if (body.len() + chunk.len()) as u64 > limit {
+            return Err(InstallationError::DownloadTooLarge(limit));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body.into())
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum ArchiveType {
     Zip,
+    /// `.tar.gz` or `.tgz`.
     TarGz,
+    TarXz,
+    TarBz2,
+    /// The driver itself, not in an archive.
+    Executable,
 }
 
-#[tracing::instrument]
-fn detect_archive_type(url: &str) -> Option<ArchiveType> {
-    if url.ends_with(".tar.gz") {
-        Some(ArchiveType::TarGz)
-    } else if url.ends_with(".zip") {
+/// Detects the archive type from the magic bytes of `content`, then from the `Content-Type` header.
+/// Urls are not used, since mirrors may serve downloads from urls without an extension.
+#[tracing::instrument(skip(content))]
+fn detect_archive_type(content: &[u8], content_type: Option<&str>) -> Option<ArchiveType> {
+    if content.starts_with(b"PK\x03\x04") {
         Some(ArchiveType::Zip)
+    } else if content.starts_with(b"\x1f\x8b") {
+        Some(ArchiveType::TarGz)
+    } else if content.starts_with(b"\xfd7zXZ\x00") {
+        Some(ArchiveType::TarXz)
+    } else if content.starts_with(b"BZh") {
+        Some(ArchiveType::TarBz2)
+    } else if ExecutableFormat::parse(content).is_some() {
+        Some(ArchiveType::Executable)
     } else {
-        None
+        archive_type_from_content_type(content_type?)
+    }
+}
+
+fn archive_type_from_content_type(content_type: &str) -> Option<ArchiveType> {
+    let mime_type = content_type.split(';').next()?.trim();
+
+    match mime_type.to_ascii_lowercase().as_str() {
+        "application/zip" | "application/x-zip-compressed" => Some(ArchiveType::Zip),
+        "application/gzip" | "application/x-gzip" | "application/x-gtar" => {
+            Some(ArchiveType::TarGz)
+        }
+        "application/x-xz" => Some(ArchiveType::TarXz),
+        "application/x-bzip2" => Some(ArchiveType::TarBz2),
+        "application/octet-stream" | "application/x-executable" => Some(ArchiveType::Executable),
+        _ => None,
     }
 }
 
+/// Limits on archives, against zip bombs.
+#[derive(Debug, Copy, Clone)]
+struct ExtractionLimits {
+    max_entries: usize,
+    max_driver_size: u64,
+    max_tarball_size: u64,
+}
+
+impl Default for ExtractionLimits {
+    fn default() -> Self {
+        ExtractionLimits {
+            max_entries: MAX_ARCHIVE_ENTRIES,
+            max_driver_size: MAX_DRIVER_SIZE,
+            max_tarball_size: MAX_TARBALL_SIZE,
+        }
+    }
+}
+
+/// Reader that fails once more than `remaining` bytes are read.
+struct LimitedReader<R> {
+    inner: R,
+    remaining: u64,
+    exceeded: bool,
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if read as u64 > self.remaining {
+            self.exceeded = true;
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Size limit exceeded",
+            ));
+        }
+
+        self.remaining -= read as u64;
+        Ok(read)
+    }
+}
+
+/// Extracts the entry named `driver_executable_name` in any directory to `driver_path`.
+/// Entry paths are only compared, never written to, so entries like `../driver` cannot escape `driver_path`.
 #[tracing::instrument(skip(content))]
 fn extract_zip(
     content: Cursor<Bytes>,
     driver_executable_name: &str,
     driver_path: &Path,
+    limits: ExtractionLimits,
 ) -> Result<u64, InstallationError> {
+    const S_IFMT: u32 = 0o170000;
+    const S_IFLNK: u32 = 0o120000;
+
     let mut archive = ZipArchive::new(content)?;
+    if archive.len() > limits.max_entries {
+        return Err(InstallationError::TooManyArchiveEntries(limits.max_entries));
+    }
 
-    let file_names = archive.file_names().map(str::to_string).collect::<Vec<_>>();
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)?;
+        if entry.is_dir() || !is_driver_entry(entry.name(), driver_executable_name) {
+            continue;
+        }
 
-    // file_names are actually file_paths
-    for file_name in file_names {
-        let file_path = Path::new(&file_name);
-        let real_file_name = file_path.file_name();
-        if real_file_name == Some(OsStr::new(driver_executable_name)) {
-            let mut driver_file = File::create(driver_path).map_err(InstallationError::Write)?;
-            let mut driver_content = archive.by_name(&file_name)?;
-            return io::copy(&mut driver_content, &mut driver_file)
-                .map_err(InstallationError::Write);
+        if entry.unix_mode().map(|mode| mode & S_IFMT) == Some(S_IFLNK) {
+            return Err(InstallationError::DriverIsLink(entry.name().to_string()));
+        }
+        if entry.size() > limits.max_driver_size {
+            return Err(InstallationError::DriverTooLarge(limits.max_driver_size));
         }
+
+        return copy_driver(entry, driver_path, limits);
     }
-    let mut driver_content = archive.by_name(driver_executable_name)?;
 
-    let mut driver_file = File::create(driver_path).map_err(InstallationError::Write)?;
-    io::copy(&mut driver_content, &mut driver_file).map_err(InstallationError::Write)
+    Err(InstallationError::ExecutableNotFoundInArchive {
+        name: driver_executable_name.to_string(),
+        entries: archive.file_names().map(str::to_string).collect(),
+    })
 }
 
-#[tracing::instrument(skip(content))]
+/// Extracts the driver from the decompressed tarball `tar`, like [`extract_zip`].
+/// Skipped entries are decompressed too, so the total decompressed size is limited.
+#[tracing::instrument(skip(tar))]
 fn extract_tarball(
-    content: Cursor<Bytes>,
+    tar: impl Read,
     driver_executable_name: &str,
     driver_path: &Path,
-) -> Result<(), InstallationError> {
-    let tar = flate2::bufread::GzDecoder::new(content);
+    limits: ExtractionLimits,
+) -> Result<u64, InstallationError> {
+    let mut tar = LimitedReader {
+        inner: tar,
+        remaining: limits.max_tarball_size,
+        exceeded: false,
+    };
+
+    let result = extract_tar_entries(&mut tar, driver_executable_name, driver_path, limits);
+    match tar.exceeded {
+        true => Err(InstallationError::TarballTooLarge(limits.max_tarball_size)),
+        false => result,
+    }
+}
+
+fn extract_tar_entries(
+    tar: impl Read,
+    driver_executable_name: &str,
+    driver_path: &Path,
+    limits: ExtractionLimits,
+) -> Result<u64, InstallationError> {
     let mut archive = Archive::new(tar);
+    let mut entries = Vec::new();
 
     for entry_result in archive.entries().map_err(InstallationError::ExtractTar)? {
-        let mut entry = entry_result.map_err(InstallationError::ExtractTar)?;
-        let entry_path = entry.path().map_err(InstallationError::ExtractTar)?;
-        let entry_file_name = entry_path.file_name();
-        if entry_file_name == Some(OsStr::new(driver_executable_name)) {
-            let mut driver_file = File::create(driver_path).map_err(InstallationError::Write)?;
-            io::copy(&mut entry, &mut driver_file).map_err(InstallationError::Write)?;
-            break;
+        if entries.len() == limits.max_entries {
+            return Err(InstallationError::TooManyArchiveEntries(limits.max_entries));
+        }
+
+        let entry = entry_result.map_err(InstallationError::ExtractTar)?;
+        let entry_path = entry
+            .path()
+            .map_err(InstallationError::ExtractTar)?
+            .to_string_lossy()
+            .into_owned();
+        let entry_type = entry.header().entry_type();
+
+        if is_driver_entry(&entry_path, driver_executable_name) && !entry_type.is_dir() {
+            if entry_type.is_symlink() || entry_type.is_hard_link() {
+                return Err(InstallationError::DriverIsLink(entry_path));
+            }
+
+            return copy_driver(entry, driver_path, limits);
         }
+
+        entries.push(entry_path);
     }
 
-    Ok(())
+    Err(InstallationError::ExecutableNotFoundInArchive {
+        name: driver_executable_name.to_string(),
+        entries,
+    })
+}
+
+fn is_driver_entry(entry_path: &str, driver_executable_name: &str) -> bool {
+    Path::new(entry_path).file_name() == Some(OsStr::new(driver_executable_name))
+}
+
+/// Writes `driver` to `driver_path`, failing once it exceeds the size limit.
+fn copy_driver(
+    driver: impl Read,
+    driver_path: &Path,
+    limits: ExtractionLimits,
+) -> Result<u64, InstallationError> {
+    let mut driver_file = File::create(driver_path).map_err(InstallationError::Write)?;
+    let written = io::copy(
+        &mut driver.take(limits.max_driver_size + 1),
+        &mut driver_file,
+    )
+    .map_err(InstallationError::Write)?;
+
+    if written > limits.max_driver_size {
+        Err(InstallationError::DriverTooLarge(limits.max_driver_size))
+    } else {
+        Ok(written)
+    }
+}
+
+#[tracing::instrument]
+fn check_executable_format(
+    driver_path: &Path,
+    platform: Platform,
+) -> Result<(), InstallationError> {
+    let format = ExecutableFormat::read(driver_path).map_err(InstallationError::Read)?;
+
+    match format {
+        Some(format) if format.runs_on(platform) => Ok(()),
+        format => Err(InstallationError::IncompatibleExecutable {
+            platform,
+            found: format.map(|format| format.to_string()),
+        }),
+    }
 }
 
 /// Error that can occur during adding execute permission.
@@ -181,3 +438,304 @@ pub(crate) fn add_execute_permission(path: &Path) -> Result<(), AddExecutePermis
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+    use std::net::Ipv4Addr;
+
+    use anyhow::Result;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use crate::platform::{Arch, Os, Platform};
+
+    use super::{
+        check_executable_format, detect_archive_type, extract_tarball, extract_zip, read_body,
+        ArchiveType, ExtractionLimits, InstallationError,
+    };
+
+    fn tarball(name: &str, content: &[u8]) -> Result<Vec<u8>> {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.append_data(&mut header, format!("driver-dir/{}", name), content)?;
+
+        Ok(builder.into_inner()?)
+    }
+
+    fn compress(mut encoder: impl Write, content: &[u8]) -> Result<()> {
+        encoder.write_all(content)?;
+        encoder.flush()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn detects_archive_type_from_content() -> Result<()> {
+        let tar = tarball("chromedriver", b"driver")?;
+
+        let mut gz = Vec::new();
+        compress(
+            flate2::write::GzEncoder::new(&mut gz, Default::default()),
+            &tar,
+        )?;
+        assert_eq!(detect_archive_type(&gz, None), Some(ArchiveType::TarGz));
+
+        #[cfg(feature = "xz")]
+        {
+            let mut xz = Vec::new();
+            compress(xz2::write::XzEncoder::new(&mut xz, 6), &tar)?;
+            assert_eq!(detect_archive_type(&xz, None), Some(ArchiveType::TarXz));
+        }
+        #[cfg(feature = "bzip2")]
+        {
+            let mut bz2 = Vec::new();
+            compress(
+                bzip2::write::BzEncoder::new(&mut bz2, Default::default()),
+                &tar,
+            )?;
+            assert_eq!(detect_archive_type(&bz2, None), Some(ArchiveType::TarBz2));
+        }
+        assert_eq!(
+            detect_archive_type(b"PK\x03\x04", None),
+            Some(ArchiveType::Zip)
+        );
+        assert_eq!(
+            detect_archive_type(&std::fs::read(std::env::current_exe()?)?, None),
+            Some(ArchiveType::Executable)
+        );
+
+        assert_eq!(
+            detect_archive_type(b"", Some("application/x-xz; charset=binary")),
+            Some(ArchiveType::TarXz)
+        );
+        assert_eq!(
+            detect_archive_type(b"<html></html>", Some("text/html")),
+            None
+        );
+        assert_eq!(detect_archive_type(b"<html></html>", None), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn extracts_compressed_tarballs() -> Result<()> {
+        let tar = tarball("geckodriver", b"driver")?;
+        let dir = tempfile::tempdir()?;
+        let driver_path = dir.path().join("geckodriver");
+
+        let mut gz = Vec::new();
+        compress(
+            flate2::write::GzEncoder::new(&mut gz, Default::default()),
+            &tar,
+        )?;
+        extract_tarball(
+            flate2::bufread::GzDecoder::new(gz.as_slice()),
+            "geckodriver",
+            &driver_path,
+            ExtractionLimits::default(),
+        )?;
+        assert_eq!(std::fs::read(&driver_path)?, b"driver");
+
+        #[cfg(feature = "xz")]
+        {
+            let mut xz = Vec::new();
+            compress(xz2::write::XzEncoder::new(&mut xz, 6), &tar)?;
+            extract_tarball(
+                xz2::bufread::XzDecoder::new(xz.as_slice()),
+                "geckodriver",
+                &driver_path,
+                ExtractionLimits::default(),
+            )?;
+            assert_eq!(std::fs::read(&driver_path)?, b"driver");
+        }
+
+        #[cfg(feature = "bzip2")]
+        {
+            let mut bz2 = Vec::new();
+            compress(
+                bzip2::write::BzEncoder::new(&mut bz2, Default::default()),
+                &tar,
+            )?;
+            extract_tarball(
+                bzip2::bufread::BzDecoder::new(bz2.as_slice()),
+                "geckodriver",
+                &driver_path,
+                ExtractionLimits::default(),
+            )?;
+            assert_eq!(std::fs::read(&driver_path)?, b"driver");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn reports_missing_executable() -> Result<()> {
+        let tar = tarball("LICENSE", b"license")?;
+        let dir = tempfile::tempdir()?;
+
+        let result = extract_tarball(
+            tar.as_slice(),
+            "geckodriver",
+            &dir.path().join("geckodriver"),
+            ExtractionLimits::default(),
+        );
+
+        match result {
+            Err(InstallationError::ExecutableNotFoundInArchive { name, entries }) => {
+                assert_eq!(name, "geckodriver");
+                assert_eq!(entries, ["driver-dir/LICENSE"]);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_links() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let driver_path = dir.path().join("chromedriver");
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.append_link(&mut header, "chromedriver", "/etc/passwd")?;
+        let tar = builder.into_inner()?;
+
+        assert!(matches!(
+            extract_tarball(
+                tar.as_slice(),
+                "chromedriver",
+                &driver_path,
+                ExtractionLimits::default()
+            ),
+            Err(InstallationError::DriverIsLink(_))
+        ));
+
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.add_symlink(
+            "chromedriver-linux64/chromedriver",
+            "/etc/passwd",
+            Default::default(),
+        )?;
+        let zip = zip.finish()?.into_inner();
+
+        assert!(matches!(
+            extract_zip(
+                Cursor::new(zip.into()),
+                "chromedriver",
+                &driver_path,
+                ExtractionLimits::default()
+            ),
+            Err(InstallationError::DriverIsLink(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn enforces_limits() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let driver_path = dir.path().join("geckodriver");
+        let tar = tarball("geckodriver", &[0; 1024])?;
+
+        let small_driver = ExtractionLimits {
+            max_driver_size: 1023,
+            ..Default::default()
+        };
+        assert!(matches!(
+            extract_tarball(tar.as_slice(), "geckodriver", &driver_path, small_driver),
+            Err(InstallationError::DriverTooLarge(1023))
+        ));
+
+        let no_entries = ExtractionLimits {
+            max_entries: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            extract_tarball(tar.as_slice(), "geckodriver", &driver_path, no_entries),
+            Err(InstallationError::TooManyArchiveEntries(0))
+        ));
+
+        // Skipped entries count towards the tarball size.
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, size) in [("LICENSE", 8192), ("geckodriver", 16)] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(size as u64);
+            header.set_cksum();
+            builder.append_data(&mut header, name, vec![0; size].as_slice())?;
+        }
+        let tar = builder.into_inner()?;
+        let small_tarball = ExtractionLimits {
+            max_tarball_size: 4096,
+            ..Default::default()
+        };
+        assert!(matches!(
+            extract_tarball(tar.as_slice(), "geckodriver", &driver_path, small_tarball),
+            Err(InstallationError::TarballTooLarge(4096))
+        ));
+        extract_tarball(
+            tar.as_slice(),
+            "geckodriver",
+            &driver_path,
+            ExtractionLimits::default(),
+        )?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn limits_download_size() -> Result<()> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let url = format!("http://127.0.0.1:{}", listener.local_addr()?.port());
+        tokio::spawn(async move {
+            // With and without Content-Length.
+            for head in ["Content-Length: 2048\r\n", ""] {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0; 4096];
+                let _ = stream.read(&mut buf).await;
+                let response = format!("HTTP/1.1 200 OK\r\n{}Connection: close\r\n\r\n", head);
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.write_all(&[0; 2048]).await;
+            }
+        });
+
+        for _ in 0..2 {
+            let response = reqwest::get(&url).await?;
+            assert!(matches!(
+                read_body(response, 1024).await,
+                Err(InstallationError::DownloadTooLarge(1024))
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_drivers_on_unknown_architectures() -> Result<()> {
+        let mut elf_x86_64 = b"\x7fELF\x02\x01\x01".to_vec();
+        elf_x86_64.resize(18, 0);
+        elf_x86_64.extend(62u16.to_le_bytes());
+
+        let dir = tempfile::tempdir()?;
+        let driver_path = dir.path().join("chromedriver");
+        std::fs::write(&driver_path, elf_x86_64)?;
+
+        check_executable_format(&driver_path, Platform::new(Os::Linux, Arch::X86_64))?;
+        assert!(matches!(
+            check_executable_format(
+                &driver_path,
+                Platform::new(Os::Linux, Arch::Other("riscv64"))
+            ),
+            Err(InstallationError::IncompatibleExecutable { found: Some(_), .. })
+        ));
+
+        Ok(())
+    }
+}
@@ -8,12 +8,18 @@
 //!
 //! If the webdriver you want to implement depends on a versioning scheme, you can implement [`version_req_url_info::VersionReqUrlInfo`] instead of [`url_info::WebdriverUrlInfo`].
 //!
+//! The checks run by [`verification_info::WebdriverVerificationInfo::verify_driver`] are [`verification_scenario::VerificationScenario`]s.
+//!
 //! See [`crate::driver_impls`] for how to implement these traits.
+//!
+//! The traits are object safe, so drivers of different types can be kept together as `Box<dyn WebdriverDownloadInfo>`.
 
 pub mod version_req_url_info;
 
 pub mod webdriver_download_info;
 
 pub mod installation_info;
+pub mod offline_verification_info;
 pub mod url_info;
 pub mod verification_info;
+pub mod verification_scenario;
@@ -0,0 +1,188 @@
+use semver::{Version, VersionReq};
+
+use crate::platform::Platform;
+use crate::traits::installation_info::WebdriverInstallationInfo;
+use crate::traits::url_info::WebdriverVersionUrl;
+use crate::traits::version_req_url_info::{VersionReqError, VersionReqUrlInfo};
+
+#[derive(thiserror::Error, Debug)]
+pub enum OfflineVerificationError {
+    #[error("Failed to get driver version")]
+    DriverVersion(VersionReqError),
+    #[error("Failed to get binary version")]
+    BinaryVersion(VersionReqError),
+    #[error("Driver version {0} is not in the support table")]
+    UnknownDriverVersion(Version),
+    #[error("Driver {driver} supports browser versions {supported}, but browser is {browser}")]
+    UnsupportedBrowser {
+        driver: Version,
+        browser: Version,
+        supported: VersionReq,
+    },
+    #[error("Driver for {0} cannot be started on this host to check its version")]
+    ForeignPlatform(Platform),
+    #[error("Failed to read support table")]
+    SupportTable(VersionReqError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// Provides information for checking an installed driver against the browser without network access or launching either.
+pub trait OfflineVerificationInfo: VersionReqUrlInfo + WebdriverInstallationInfo {
+    /// Version of the installed driver, usually from its `--version` output.
+    fn driver_version(&self) -> Result<Version, VersionReqError>;
+
+    /// Browser versions that `driver_version` supports.
+    ///
+    /// Defaults to the `version_req` of `driver_version` in [`VersionReqUrlInfo::cached_version_urls`]
+    /// or [`VersionReqUrlInfo::snapshot_version_urls`]. If neither lists it, browsers with the same major version
+    /// are supported, such as `=120` for chromedriver 120.0.6099.109.
+    fn supported_browser_versions(
+        &self,
+        driver_version: &Version,
+    ) -> Result<VersionReq, OfflineVerificationError> {
+        let find = |version_urls: Vec<WebdriverVersionUrl>| {
+            version_urls
+                .into_iter()
+                .find(|version_url| &version_url.webdriver_version == driver_version)
+                .map(|version_url| version_url.version_req)
+        };
+
+        let listed = self
+            .cached_version_urls()
+            .ok()
+            .and_then(find)
+            .or_else(|| self.snapshot_version_urls().ok().and_then(find));
+        if let Some(version_req) = listed {
+            return Ok(version_req);
+        }
+
+        VersionReq::parse(&format!("={}", driver_version.major))
+            .map_err(|e| OfflineVerificationError::Other(e.into()))
+    }
+
+    /// Verifies that the installed driver supports [`VersionReqUrlInfo::binary_version`].
+    #[tracing::instrument(skip(self))]
+    fn verify_driver_offline(&self) -> Result<(), OfflineVerificationError> {
+        let target_platform = self.target_platform();
+        if target_platform != Platform::current() {
+            return Err(OfflineVerificationError::ForeignPlatform(target_platform));
+        }
+
+        let driver = self
+            .driver_version()
+            .map_err(OfflineVerificationError::DriverVersion)?;
+        let browser = self
+            .binary_version()
+            .map_err(OfflineVerificationError::BinaryVersion)?;
+        let supported = self.supported_browser_versions(&driver)?;
+
+        if supported.matches(&browser) {
+            Ok(())
+        } else {
+            Err(OfflineVerificationError::UnsupportedBrowser {
+                driver,
+                browser,
+                supported,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use async_trait::async_trait;
+    use semver::{Version, VersionReq};
+
+    use crate::traits::installation_info::WebdriverInstallationInfo;
+    use crate::traits::url_info::{UrlError, WebdriverVersionUrl};
+    use crate::traits::version_req_url_info::{VersionReqError, VersionReqUrlInfo};
+
+    use super::{OfflineVerificationError, OfflineVerificationInfo};
+
+    struct FixedVersions {
+        driver: Version,
+        browser: Version,
+        snapshot: Vec<WebdriverVersionUrl>,
+    }
+
+    #[async_trait]
+    impl VersionReqUrlInfo for FixedVersions {
+        fn binary_version(&self) -> Result<Version, VersionReqError> {
+            Ok(self.browser.clone())
+        }
+
+        async fn driver_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+            Ok(vec![])
+        }
+
+        fn snapshot_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+            Ok(self.snapshot.clone())
+        }
+    }
+
+    impl WebdriverInstallationInfo for FixedVersions {
+        fn driver_install_path(&self) -> &Path {
+            Path::new("")
+        }
+
+        fn driver_executable_name(&self) -> &str {
+            ""
+        }
+    }
+
+    impl OfflineVerificationInfo for FixedVersions {
+        fn driver_version(&self) -> Result<Version, VersionReqError> {
+            Ok(self.driver.clone())
+        }
+    }
+
+    #[test]
+    fn default_rule_matches_major_version() {
+        let matching = FixedVersions {
+            driver: Version::new(120, 0, 6099),
+            browser: Version::new(120, 0, 6100),
+            snapshot: vec![],
+        };
+        assert!(matching.verify_driver_offline().is_ok());
+
+        let mismatching = FixedVersions {
+            driver: Version::new(120, 0, 6099),
+            browser: Version::new(119, 0, 6045),
+            snapshot: vec![],
+        };
+        assert!(matches!(
+            mismatching.verify_driver_offline(),
+            Err(OfflineVerificationError::UnsupportedBrowser { .. })
+        ));
+    }
+
+    #[test]
+    fn listed_versions_use_their_version_req() {
+        let listed = WebdriverVersionUrl {
+            version_req: VersionReq::parse("^120.0.6099").unwrap(),
+            webdriver_version: Version::new(120, 0, 6099),
+            url: String::new(),
+            stale: false,
+        };
+
+        let supported = FixedVersions {
+            driver: Version::new(120, 0, 6099),
+            browser: Version::new(120, 0, 6100),
+            snapshot: vec![listed.clone()],
+        };
+        assert!(supported.verify_driver_offline().is_ok());
+
+        let older_browser = FixedVersions {
+            driver: Version::new(120, 0, 6099),
+            browser: Version::new(120, 0, 6000),
+            snapshot: vec![listed],
+        };
+        assert!(matches!(
+            older_browser.verify_driver_offline(),
+            Err(OfflineVerificationError::UnsupportedBrowser { .. })
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use semver::{Version, VersionReq};
 
+use crate::platform::Platform;
 use crate::traits::version_req_url_info::VersionReqError;
 
 /// Error that can occur while getting the urls.
@@ -16,15 +17,43 @@ pub enum UrlError {
     Version(#[from] lenient_semver::parser::OwnedError),
     #[error(transparent)]
     JsonParse(#[from] serde_json::Error),
+    /// The driver has no upstream build for `platform`. `hint` suggests an alternative, such as a distribution package.
+    #[error(
+        "No upstream driver build for platform {platform}{}",
+        .hint.as_ref().map(|hint| format!(". {}", hint)).unwrap_or_default()
+    )]
+    UnsupportedPlatform {
+        platform: Platform,
+        hint: Option<String>,
+    },
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+/// Use [`WebdriverVersionUrl::new`] to create one outside this crate.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub struct WebdriverVersionUrl {
     pub version_req: VersionReq,
     pub webdriver_version: Version,
     pub url: String,
+    /// Whether this comes from metadata bundled with the crate, because the remote source was unreachable.
+    pub stale: bool,
+}
+
+impl WebdriverVersionUrl {
+    pub fn new(
+        version_req: VersionReq,
+        webdriver_version: Version,
+        url: impl Into<String>,
+    ) -> Self {
+        WebdriverVersionUrl {
+            version_req,
+            webdriver_version,
+            url: url.into(),
+            stale: false,
+        }
+    }
 }
 
 /// Provides information for determining which url to download.
@@ -32,4 +61,9 @@ pub struct WebdriverVersionUrl {
 pub trait WebdriverUrlInfo {
     /// Lists viable [`WebdriverVersionUrl`]s, up to `limit`.
     async fn version_urls(&self, limit: usize) -> Result<Vec<WebdriverVersionUrl>, UrlError>;
+
+    /// Version of the browser the urls are chosen for, if it can be determined.
+    fn browser_version(&self) -> Option<Version> {
+        None
+    }
 }
@@ -1,28 +1,189 @@
-use std::ffi::OsStr;
 use std::fmt::Debug;
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use fantoccini::wd::Capabilities;
-use fantoccini::Locator;
+use semver::Version;
+use serde_json::{Map, Value};
+use tokio::time::{timeout_at, Instant};
 
-const WEBDRIVER_WAIT_DURATION: Duration = Duration::from_millis(500);
-const MAX_RETRIES: usize = 10;
+use crate::driver_process::{
+    reported_version, DriverLogTail, DriverProcess, DriverProcessError, DriverProcessOptions,
+    DriverStatus, SHUTDOWN_TIMEOUT,
+};
+use crate::local_server::LocalPageServer;
+use crate::traits::verification_scenario::{default_scenarios, VerificationScenario};
+
+/// Page served by [`VerificationTarget::default`].
+pub const DEFAULT_VERIFICATION_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><title>webdriver-downloader</title></head>
+<body><p id="webdriver-downloader">webdriver-downloader</p></body>
+</html>"#;
 
 /// Error that can occur during verification.
 #[derive(thiserror::Error, Debug)]
 pub enum VerificationError {
     #[error("Failed to start driver: {0}")]
     Start(#[from] std::io::Error),
-    #[error("Failed to connect to driver: {0}")]
-    Connect(#[from] fantoccini::error::NewSessionError),
+    #[error("Failed to serve verification page: {0}")]
+    PageServer(std::io::Error),
+    #[error("Failed to connect to driver: {source}{logs}")]
+    Connect {
+        source: fantoccini::error::NewSessionError,
+        logs: DriverLogTail,
+    },
     #[error("Driver test failed to pass: {0}")]
     Navigate(#[from] fantoccini::error::CmdError),
+    /// A [`VerificationScenario`] found the driver misbehaving.
+    #[error("Verification scenario `{name}` failed: {reason}")]
+    Scenario { name: String, reason: String },
+    /// [`WebdriverVerificationInfo::test_client`] failed, with the driver's logs at the time.
+    #[error("{source}{logs}")]
+    Test {
+        source: Box<VerificationError>,
+        logs: DriverLogTail,
+    },
+    #[error(transparent)]
+    DriverProcess(#[from] DriverProcessError),
+    #[error("Verification did not finish within {0:?}")]
+    Timeout(Duration),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+/// Page that [`WebdriverVerificationInfo::verify_driver`] navigates to.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum VerificationTarget {
+    /// Html served from an in-process http server on localhost, so verification works without network access.
+    LocalPage(String),
+    /// Url to navigate to, such as `https://www.example.com` or a `data:` url.
+    /// Set [`NavigateScenario::marker`](crate::traits::verification_scenario::NavigateScenario::marker) to an element of the page.
+    Url(String),
+}
+
+impl Default for VerificationTarget {
+    /// Serves [`DEFAULT_VERIFICATION_PAGE`] locally.
+    fn default() -> Self {
+        VerificationTarget::LocalPage(DEFAULT_VERIFICATION_PAGE.to_string())
+    }
+}
+
+/// Timeouts used when starting a driver and verifying it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct VerificationConfig {
+    /// How long to wait for the driver's `/status` endpoint to report ready.
+    pub startup_timeout: Duration,
+    /// Interval between `/status` polls, and between session creation attempts.
+    pub poll_interval: Duration,
+    /// How long to keep trying to create a session, which includes launching the browser.
+    pub session_timeout: Duration,
+    /// Upper bound for the whole of [`WebdriverVerificationInfo::verify_driver`].
+    pub deadline: Duration,
+}
+
+impl Default for VerificationConfig {
+    fn default() -> Self {
+        VerificationConfig {
+            startup_timeout: Duration::from_secs(10),
+            poll_interval: Duration::from_millis(250),
+            session_timeout: Duration::from_secs(15),
+            deadline: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Browser settings merged into the capabilities used for verification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrowserOptions {
+    /// Whether to pass the browser's headless argument. Disable to verify headed, such as under Xvfb.
+    pub headless: bool,
+    /// Arguments appended after the default ones, such as `--disable-dev-shm-usage`.
+    pub args: Vec<String>,
+    /// Browser preferences, merged into the `prefs` of the browser options.
+    pub prefs: Map<String, Value>,
+    /// Extra capabilities, which override generated ones.
+    /// Objects under the browser options key, such as `goog:chromeOptions`, are merged key by key.
+    pub capabilities: Capabilities,
+}
+
+impl Default for BrowserOptions {
+    fn default() -> Self {
+        BrowserOptions {
+            headless: true,
+            args: Vec::new(),
+            prefs: Map::new(),
+            capabilities: Capabilities::new(),
+        }
+    }
+}
+
+impl BrowserOptions {
+    /// Builds capabilities with `browser_options` under `options_key`, after applying these options to it.
+    pub fn to_capabilities(
+        &self,
+        options_key: &str,
+        mut browser_options: Map<String, Value>,
+    ) -> Capabilities {
+        if !self.args.is_empty() {
+            let args = browser_options
+                .entry("args")
+                .or_insert_with(|| Value::Array(Vec::new()));
+            if let Value::Array(args) = args {
+                args.extend(self.args.iter().cloned().map(Value::String));
+            }
+        }
+
+        if !self.prefs.is_empty() {
+            let prefs = browser_options
+                .entry("prefs")
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(prefs) = prefs {
+                prefs.extend(self.prefs.clone());
+            }
+        }
+
+        let mut capabilities = Capabilities::new();
+        capabilities.insert(options_key.to_string(), Value::Object(browser_options));
+
+        for (key, value) in &self.capabilities {
+            match (capabilities.get_mut(key), value) {
+                (Some(Value::Object(existing)), Value::Object(value)) => {
+                    existing.extend(value.clone())
+                }
+                _ => {
+                    capabilities.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        capabilities
+    }
+}
+
+/// How thoroughly an installed driver is checked.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum VerificationLevel {
+    /// Driver executable exists.
+    Exists,
+    /// Driver starts and reports ready on its `/status` endpoint.
+    /// Does not launch the browser or access the network.
+    Status,
+    /// Driver passes [`WebdriverVerificationInfo::verify_driver`], which launches the browser.
+    #[default]
+    Full,
+}
+
+/// Result of [`WebdriverVerificationInfo::check_driver_status`].
+#[derive(Debug, Clone)]
+pub struct DriverStatusReport {
+    pub status: DriverStatus,
+    /// Version reported by the driver's `/status` endpoint, or by `--version` if the endpoint does not report one.
+    pub driver_version: Option<Version>,
+}
+
 /// Provides information for verifying a webdriver.
 #[async_trait]
 pub trait WebdriverVerificationInfo {
@@ -30,101 +191,211 @@ pub trait WebdriverVerificationInfo {
     /// Some driver options such as browser path can be provided by capabilities.
     fn driver_capabilities(&self) -> Option<Capabilities>;
 
-    /// Verifies driver using [`Self::test_client`].
+    /// Path to the browser used for verification, if any.
+    fn browser_path(&self) -> Option<&Path> {
+        None
+    }
+
+    /// Page to test the driver with. Defaults to a page served from localhost.
+    fn verification_target(&self) -> VerificationTarget {
+        VerificationTarget::default()
+    }
+
+    /// Timeouts to use for verification.
+    fn verification_config(&self) -> VerificationConfig {
+        VerificationConfig::default()
+    }
+
+    /// Checks run by [`Self::test_client`], in order. Defaults to navigating to the verification target.
+    fn verification_scenarios(&self) -> Vec<Arc<dyn VerificationScenario>> {
+        default_scenarios()
+    }
+
+    /// Flag the driver accepts for writing its log to a file, such as chromedriver's `--log-path`.
+    /// If provided, the end of the log is attached to verification errors.
+    fn driver_log_file_flag(&self) -> Option<&str> {
+        None
+    }
+
+    /// Verifies driver by calling [`Self::test_client`] with the url of [`Self::verification_target`].
+    /// The session is closed and the driver shut down even if [`VerificationConfig::deadline`] passes.
     #[tracing::instrument(skip(self))]
-    async fn verify_driver<P: AsRef<Path> + Debug + Sync>(
-        &self,
-        driver_path: &P,
-    ) -> Result<(), VerificationError> {
-        let port = get_random_available_port();
-        let mut child = tokio::process::Command::new(OsStr::new(driver_path.as_ref()))
-            .arg(&format!("--port={}", port))
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::piped())
-            .kill_on_drop(true)
-            .spawn()?;
-
-        tokio::time::sleep(WEBDRIVER_WAIT_DURATION).await;
-
-        let mut current_tries = 0;
-        #[cfg(feature = "native-tls")]
-        let mut new_client = fantoccini::ClientBuilder::native();
-        #[cfg(feature = "rustls-tls")]
-        let mut new_client = fantoccini::ClientBuilder::rustls();
-        let client = loop {
-            let connect_result = if let Some(capabilities) = self.driver_capabilities() {
-                new_client
-                    .capabilities(capabilities)
-                    .connect(&format!("http://localhost:{}", port))
-                    .await
-            } else {
-                new_client
-                    .connect(&format!("http://localhost:{}", port))
+    async fn verify_driver(&self, driver_path: &Path) -> Result<(), VerificationError> {
+        let config = self.verification_config();
+        let deadline = Instant::now() + config.deadline;
+        let timed_out = |_| VerificationError::Timeout(config.deadline);
+
+        let (url, _server) = match self.verification_target() {
+            VerificationTarget::LocalPage(html) => {
+                let server = timeout_at(deadline, LocalPageServer::start(html))
                     .await
-            };
+                    .map_err(timed_out)?
+                    .map_err(VerificationError::PageServer)?;
+                (server.url().to_string(), Some(server))
+            }
+            VerificationTarget::Url(url) => (url, None),
+        };
 
-            match connect_result {
-                Ok(client) => break client,
-                Err(e) => {
-                    if current_tries >= MAX_RETRIES {
-                        return Err(VerificationError::Connect(e));
-                    }
+        let options = DriverProcessOptions {
+            port: None,
+            config,
+            log_file_flag: self.driver_log_file_flag().map(str::to_string),
+        };
+        let driver = timeout_at(
+            deadline,
+            DriverProcess::start_with_options(driver_path, options),
+        )
+        .await
+        .map_err(timed_out)??;
 
-                    current_tries += 1;
-                    tokio::time::sleep(WEBDRIVER_WAIT_DURATION).await;
-                }
+        let client = match timeout_at(deadline, driver.connect(self.driver_capabilities())).await {
+            Ok(Ok(client)) => Some(client),
+            Ok(Err(source)) => {
+                let error = VerificationError::Connect {
+                    source,
+                    logs: driver.log_tail(),
+                };
+                shutdown(None, driver).await;
+                return Err(error);
             }
+            Err(_) => None,
         };
 
-        let test_result = Self::test_client(&client).await;
+        let test_result = match &client {
+            Some(client) => match timeout_at(deadline, self.test_client(client, &url)).await {
+                Ok(result) => result.map_err(|e| VerificationError::Test {
+                    source: Box::new(e),
+                    logs: driver.log_tail(),
+                }),
+                Err(_) => Err(VerificationError::Timeout(config.deadline)),
+            },
+            None => Err(VerificationError::Timeout(config.deadline)),
+        };
 
-        if let Err(e) = client.close().await {
-            println!("Failed to close client: {}", e);
-        }
+        shutdown(client, driver).await;
+
+        test_result
+    }
+
+    /// Starts the driver and waits until its `/status` endpoint reports ready, without launching the browser.
+    #[tracing::instrument(skip(self))]
+    async fn check_driver_status(
+        &self,
+        driver_path: &Path,
+    ) -> Result<DriverStatusReport, VerificationError> {
+        let driver =
+            DriverProcess::start_with_config(driver_path, None, self.verification_config()).await?;
+        let status = driver.status().await;
 
-        if let Err(e) = child.kill().await {
-            println!("Failed to kill driver: {}", e);
+        if let Err(e) = driver.shutdown().await {
+            tracing::warn!("Failed to kill driver: {}", e);
         }
 
-        test_result
+        let status = status?;
+        let driver_version = status
+            .version()
+            .and_then(|version| lenient_semver::parse(version).ok())
+            .or_else(|| reported_version(driver_path).ok());
+
+        Ok(DriverStatusReport {
+            status,
+            driver_version,
+        })
     }
 
-    /// Tests driver using [`fantoccini::Client`]. Default implementation navigates to `example.com`.
-    async fn test_client(client: &fantoccini::Client) -> Result<(), VerificationError> {
-        client.goto("https://www.example.com").await?;
-        client.find(Locator::Css("html")).await?;
+    /// Tests driver using [`fantoccini::Client`]. Default implementation runs [`Self::verification_scenarios`] against `url`.
+    async fn test_client(
+        &self,
+        client: &fantoccini::Client,
+        url: &str,
+    ) -> Result<(), VerificationError> {
+        for scenario in self.verification_scenarios() {
+            tracing::debug!("Running verification scenario {}", scenario.name());
+            scenario.run(client, url).await?;
+        }
 
         Ok(())
     }
 }
 
-fn get_random_available_port() -> u16 {
-    use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener};
+/// Closes the session, if any, and shuts down the driver, logging failures.
+/// Closing is given [`SHUTDOWN_TIMEOUT`], since it waits for the browser, which may be hung.
+async fn shutdown(client: Option<fantoccini::Client>, driver: DriverProcess) {
+    if let Some(client) = client {
+        match tokio::time::timeout(SHUTDOWN_TIMEOUT, client.close()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => tracing::warn!("Failed to close client: {}", e),
+            Err(_) => tracing::warn!("Failed to close client: timed out"),
+        }
+    }
 
-    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 0);
-    let listener = TcpListener::bind(addr).unwrap();
-    listener.local_addr().unwrap().port()
+    if let Err(e) = driver.shutdown().await {
+        tracing::warn!("Failed to kill driver: {}", e);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::net::TcpListener;
+    use serde_json::json;
 
-    use anyhow::Result;
-
-    use super::get_random_available_port;
+    use super::BrowserOptions;
 
     #[test]
-    fn test_get_random_available_port() -> Result<()> {
-        let port = get_random_available_port();
+    fn browser_options_merge_into_capabilities() {
+        let options = BrowserOptions {
+            headless: true,
+            args: vec!["--disable-dev-shm-usage".to_string()],
+            prefs: json!({"intl.accept_languages": "en"})
+                .as_object()
+                .unwrap()
+                .clone(),
+            capabilities: json!({
+                "acceptInsecureCerts": true,
+                "goog:chromeOptions": {"binary": "/other/chrome"},
+            })
+            .as_object()
+            .unwrap()
+            .clone(),
+        };
+        let browser_options = json!({
+            "binary": "/usr/bin/chrome",
+            "args": ["-headless"],
+            "prefs": {"download.prompt_for_download": false},
+        });
 
-        // Check if the port number is within the valid range
-        assert!(port > 0, "Port number should be within the valid range");
+        let capabilities = options.to_capabilities(
+            "goog:chromeOptions",
+            browser_options.as_object().unwrap().clone(),
+        );
 
-        // Check if the port is actually available
-        let addr = format!("127.0.0.1:{}", port);
-        TcpListener::bind(addr)?;
+        assert_eq!(
+            serde_json::Value::Object(capabilities),
+            json!({
+                "acceptInsecureCerts": true,
+                "goog:chromeOptions": {
+                    "binary": "/other/chrome",
+                    "args": ["-headless", "--disable-dev-shm-usage"],
+                    "prefs": {
+                        "download.prompt_for_download": false,
+                        "intl.accept_languages": "en",
+                    },
+                },
+            })
+        );
+    }
 
-        Ok(())
+    #[test]
+    fn default_browser_options_keep_capabilities() {
+        let browser_options = json!({"binary": "/usr/bin/firefox", "args": ["-headless"]});
+
+        let capabilities = BrowserOptions::default().to_capabilities(
+            "moz:firefoxOptions",
+            browser_options.as_object().unwrap().clone(),
+        );
+
+        assert_eq!(
+            serde_json::Value::Object(capabilities),
+            json!({"moz:firefoxOptions": browser_options})
+        );
     }
 }
@@ -0,0 +1,285 @@
+//! Checks run against a browser session during [`verify_driver`](super::verification_info::WebdriverVerificationInfo::verify_driver).
+//!
+//! Scenarios run in order on the same [`fantoccini::Client`].
+//! Implement [`VerificationScenario`] to add your own, or use the built-in ones:
+//!
+//! | Name          | Scenario                  |
+//! |---------------|---------------------------|
+//! | `navigate`    | [`NavigateScenario`]      |
+//! | `script`      | [`ExecuteScriptScenario`] |
+//! | `screenshot`  | [`ScreenshotScenario`]    |
+//! | `window-size` | [`WindowSizeScenario`]    |
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use fantoccini::{Client, Locator};
+use serde_json::{json, Value};
+
+use crate::traits::verification_info::VerificationError;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Names accepted by [`builtin_scenario`].
+pub const BUILTIN_SCENARIOS: [&str; 4] = ["navigate", "script", "screenshot", "window-size"];
+
+/// A check run against a browser session to verify a driver.
+#[async_trait]
+pub trait VerificationScenario: Debug + Send + Sync {
+    /// Short name of the scenario, used in error messages.
+    fn name(&self) -> &str;
+
+    /// Runs the scenario. `url` is the url of the [`VerificationTarget`](super::verification_info::VerificationTarget).
+    async fn run(&self, client: &Client, url: &str) -> Result<(), VerificationError>;
+}
+
+/// Element a page must contain, and its text.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PageMarker {
+    /// Css selector of the element.
+    pub selector: String,
+    pub text: String,
+}
+
+impl Default for PageMarker {
+    /// The `#webdriver-downloader` element of [`DEFAULT_VERIFICATION_PAGE`](super::verification_info::DEFAULT_VERIFICATION_PAGE).
+    fn default() -> Self {
+        PageMarker {
+            selector: "#webdriver-downloader".to_string(),
+            text: "webdriver-downloader".to_string(),
+        }
+    }
+}
+
+/// Navigates to the verification url and checks that the page has loaded by looking up its marker.
+/// Error pages and blank tabs don't have the marker.
+#[derive(Debug, Clone, Default)]
+pub struct NavigateScenario {
+    /// Defaults to the marker of [`DEFAULT_VERIFICATION_PAGE`](super::verification_info::DEFAULT_VERIFICATION_PAGE).
+    /// Set it to one of your page, or `None` to skip the check, when using [`VerificationTarget::Url`](super::verification_info::VerificationTarget::Url).
+    pub marker: Option<PageMarker>,
+}
+
+impl NavigateScenario {
+    /// Checks the text of the marker element, `None` if the page doesn't have one.
+    fn check_marker(
+        &self,
+        marker: &PageMarker,
+        text: Option<&str>,
+    ) -> Result<(), VerificationError> {
+        let reason = match text {
+            Some(text) if text == marker.text => return Ok(()),
+            Some(text) => format!(
+                "{} has text {:?}, expected {:?}",
+                marker.selector, text, marker.text
+            ),
+            None => format!("page has no {} element", marker.selector),
+        };
+
+        Err(VerificationError::Scenario {
+            name: self.name().to_string(),
+            reason,
+        })
+    }
+}
+
+#[async_trait]
+impl VerificationScenario for NavigateScenario {
+    fn name(&self) -> &str {
+        "navigate"
+    }
+
+    async fn run(&self, client: &Client, url: &str) -> Result<(), VerificationError> {
+        client.goto(url).await?;
+
+        if let Some(marker) = &self.marker {
+            let text = match client.find(Locator::Css(&marker.selector)).await {
+                Ok(element) => Some(element.text().await?),
+                Err(e) if e.is_miss() => None,
+                Err(e) => return Err(e.into()),
+            };
+
+            self.check_marker(marker, text.as_deref())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes a script and compares its return value.
+#[derive(Debug, Clone)]
+pub struct ExecuteScriptScenario {
+    pub script: String,
+    /// Value the script must return. Any value is accepted if `None`.
+    pub expected: Option<Value>,
+}
+
+impl Default for ExecuteScriptScenario {
+    /// Evaluates `1 + 1`.
+    fn default() -> Self {
+        ExecuteScriptScenario {
+            script: "return 1 + 1;".to_string(),
+            expected: Some(json!(2)),
+        }
+    }
+}
+
+#[async_trait]
+impl VerificationScenario for ExecuteScriptScenario {
+    fn name(&self) -> &str {
+        "script"
+    }
+
+    async fn run(&self, client: &Client, _url: &str) -> Result<(), VerificationError> {
+        let value = client.execute(&self.script, vec![]).await?;
+
+        match &self.expected {
+            Some(expected) if expected != &value => Err(VerificationError::Scenario {
+                name: self.name().to_string(),
+                reason: format!("script returned {}, expected {}", value, expected),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Captures a screenshot and checks that it is a png image.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ScreenshotScenario;
+
+#[async_trait]
+impl VerificationScenario for ScreenshotScenario {
+    fn name(&self) -> &str {
+        "screenshot"
+    }
+
+    async fn run(&self, client: &Client, _url: &str) -> Result<(), VerificationError> {
+        let screenshot = client.screenshot().await?;
+
+        if screenshot.starts_with(PNG_SIGNATURE) {
+            Ok(())
+        } else {
+            Err(VerificationError::Scenario {
+                name: self.name().to_string(),
+                reason: format!("screenshot is not a png image ({} bytes)", screenshot.len()),
+            })
+        }
+    }
+}
+
+/// Resizes the window and checks that the new size is reported back.
+#[derive(Debug, Copy, Clone)]
+pub struct WindowSizeScenario {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for WindowSizeScenario {
+    fn default() -> Self {
+        WindowSizeScenario {
+            width: 800,
+            height: 600,
+        }
+    }
+}
+
+#[async_trait]
+impl VerificationScenario for WindowSizeScenario {
+    fn name(&self) -> &str {
+        "window-size"
+    }
+
+    async fn run(&self, client: &Client, _url: &str) -> Result<(), VerificationError> {
+        client.set_window_size(self.width, self.height).await?;
+        let (width, height) = client.get_window_size().await?;
+
+        if (width, height) == (self.width as u64, self.height as u64) {
+            Ok(())
+        } else {
+            Err(VerificationError::Scenario {
+                name: self.name().to_string(),
+                reason: format!(
+                    "window size is {}x{}, expected {}x{}",
+                    width, height, self.width, self.height
+                ),
+            })
+        }
+    }
+}
+
+/// Scenarios used when none are configured: [`NavigateScenario`] only.
+pub fn default_scenarios() -> Vec<Arc<dyn VerificationScenario>> {
+    vec![Arc::new(NavigateScenario::default())]
+}
+
+/// Returns the built-in scenario with default settings for one of [`BUILTIN_SCENARIOS`].
+pub fn builtin_scenario(name: &str) -> Option<Arc<dyn VerificationScenario>> {
+    match name {
+        "navigate" => Some(Arc::new(NavigateScenario::default())),
+        "script" => Some(Arc::new(ExecuteScriptScenario::default())),
+        "screenshot" => Some(Arc::new(ScreenshotScenario)),
+        "window-size" => Some(Arc::new(WindowSizeScenario::default())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::traits::verification_info::{VerificationError, DEFAULT_VERIFICATION_PAGE};
+
+    use super::{
+        builtin_scenario, default_scenarios, NavigateScenario, PageMarker, BUILTIN_SCENARIOS,
+    };
+
+    #[test]
+    fn builtin_scenarios_match_names() {
+        for name in BUILTIN_SCENARIOS {
+            let scenario = builtin_scenario(name).expect("Missing built-in scenario");
+            assert_eq!(scenario.name(), name);
+        }
+
+        assert!(builtin_scenario("unknown").is_none());
+    }
+
+    #[test]
+    fn default_scenarios_navigate() {
+        let names: Vec<_> = default_scenarios()
+            .iter()
+            .map(|scenario| scenario.name().to_string())
+            .collect();
+
+        assert_eq!(names, ["navigate"]);
+    }
+
+    #[test]
+    fn default_marker_is_on_default_page() {
+        let marker = PageMarker::default();
+
+        assert!(DEFAULT_VERIFICATION_PAGE.contains(&format!(
+            r#"<p id="{}">{}</p>"#,
+            marker.selector.trim_start_matches('#'),
+            marker.text
+        )));
+    }
+
+    #[test]
+    fn navigate_fails_on_other_pages() {
+        let scenario = NavigateScenario::default();
+        let marker = PageMarker::default();
+
+        assert!(scenario
+            .check_marker(&marker, Some("webdriver-downloader"))
+            .is_ok());
+        // An error page or blank tab, without the marker element.
+        assert!(matches!(
+            scenario.check_marker(&marker, None),
+            Err(VerificationError::Scenario { .. })
+        ));
+        // Another page that happens to use the id.
+        assert!(matches!(
+            scenario.check_marker(&marker, Some("Example Domain")),
+            Err(VerificationError::Scenario { .. })
+        ));
+    }
+}
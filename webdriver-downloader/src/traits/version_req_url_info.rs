@@ -38,7 +38,10 @@ pub trait VersionReqUrlInfo: WebdriverUrlInfo {
         binary_version: &Version,
         left: &WebdriverVersionUrl,
         right: &WebdriverVersionUrl,
-    ) -> Ordering {
+    ) -> Ordering
+    where
+        Self: Sized,
+    {
         let left_match = left.version_req.matches(binary_version);
         let right_match = right.version_req.matches(binary_version);
         match (left_match, right_match) {
@@ -50,6 +53,18 @@ pub trait VersionReqUrlInfo: WebdriverUrlInfo {
 
     /// [`WebdriverVersionUrl`]s, probably parsed from driver's download page.
     async fn driver_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError>;
+
+    /// [`WebdriverVersionUrl`]s from metadata bundled with the crate.
+    /// Used if [`driver_version_urls`](VersionReqUrlInfo::driver_version_urls) fails. Empty by default.
+    fn snapshot_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+        Ok(Vec::new())
+    }
+
+    /// [`WebdriverVersionUrl`]s from previously downloaded metadata, read without network access.
+    /// Empty by default.
+    fn cached_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+        Ok(Vec::new())
+    }
 }
 
 #[async_trait]
@@ -58,7 +73,26 @@ where
     T: VersionReqUrlInfo + Sync,
 {
     async fn version_urls(&self, limit: usize) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
-        let url_infos = self.driver_version_urls().await?;
+        let url_infos = match self.driver_version_urls().await {
+            Ok(url_infos) => url_infos,
+            Err(e) => match self.snapshot_version_urls() {
+                Ok(snapshot) if !snapshot.is_empty() => {
+                    tracing::warn!(
+                        "Failed to get driver versions, using bundled snapshot instead: {}",
+                        e
+                    );
+
+                    snapshot
+                        .into_iter()
+                        .map(|version_url| WebdriverVersionUrl {
+                            stale: true,
+                            ..version_url
+                        })
+                        .collect()
+                }
+                _ => return Err(e),
+            },
+        };
 
         let cmp: Box<dyn Fn(&WebdriverVersionUrl, &WebdriverVersionUrl) -> Ordering> =
             match self.binary_version() {
@@ -102,6 +136,10 @@ where
 
         Ok(versions)
     }
+
+    fn browser_version(&self) -> Option<Version> {
+        self.binary_version().ok()
+    }
 }
 
 #[cfg(test)]
@@ -118,6 +156,25 @@ mod tests {
         version_urls: Vec<WebdriverVersionUrl>,
     }
 
+    struct MockUnreachableUrlInfo {
+        snapshot: Vec<WebdriverVersionUrl>,
+    }
+
+    #[async_trait]
+    impl VersionReqUrlInfo for MockUnreachableUrlInfo {
+        fn binary_version(&self) -> Result<Version, VersionReqError> {
+            Err(VersionReqError::Other(anyhow::anyhow!("No version hint")))
+        }
+
+        async fn driver_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+            Err(anyhow::anyhow!("unreachable").into())
+        }
+
+        fn snapshot_version_urls(&self) -> Result<Vec<WebdriverVersionUrl>, UrlError> {
+            Ok(self.snapshot.clone())
+        }
+    }
+
     #[async_trait]
     impl VersionReqUrlInfo for MockBinaryMajorVersionHintUrlInfo {
         fn binary_version(&self) -> Result<Version, VersionReqError> {
@@ -137,6 +194,7 @@ mod tests {
             version_req: VersionReq::parse(&format!("^{}", version_string)).unwrap(),
             url: Default::default(),
             webdriver_version: Version::parse(&version_string).unwrap(),
+            stale: false,
         }
     }
 
@@ -195,4 +253,23 @@ mod tests {
             ]
         )
     }
+
+    #[tokio::test]
+    async fn falls_back_to_snapshot() {
+        let mock_info = MockUnreachableUrlInfo {
+            snapshot: vec![dummy_version_info(Version::new(1, 0, 0))],
+        };
+
+        let version_urls = mock_info.version_urls(5).await.unwrap();
+
+        assert_eq!(version_urls.len(), 1);
+        assert!(version_urls[0].stale);
+    }
+
+    #[tokio::test]
+    async fn fails_without_snapshot() {
+        let mock_info = MockUnreachableUrlInfo { snapshot: vec![] };
+
+        assert!(mock_info.version_urls(5).await.is_err());
+    }
 }
@@ -1,12 +1,18 @@
 use std::io;
+use std::path::PathBuf;
 
 use async_trait::async_trait;
+use semver::Version;
 use tempfile::TempDir;
 
+use crate::driver_process::{DriverProcess, DriverProcessError, DriverSession};
 use crate::os_specific::DefaultPathError;
+use crate::platform::Platform;
 use crate::traits::installation_info::{InstallationError, WebdriverInstallationInfo};
-use crate::traits::url_info::{UrlError, WebdriverUrlInfo};
-use crate::traits::verification_info::{VerificationError, WebdriverVerificationInfo};
+use crate::traits::url_info::{UrlError, WebdriverUrlInfo, WebdriverVersionUrl};
+use crate::traits::verification_info::{
+    VerificationError, VerificationLevel, WebdriverVerificationInfo,
+};
 
 /// Information required to download, verify, install driver.
 ///
@@ -15,15 +21,53 @@ use crate::traits::verification_info::{VerificationError, WebdriverVerificationI
 pub trait WebdriverDownloadInfo:
     WebdriverUrlInfo + WebdriverInstallationInfo + WebdriverVerificationInfo + Sync
 {
-    /// Check if the driver is installed.
+    /// Check if the driver is installed, using [`VerificationLevel::Full`].
     async fn is_installed(&self) -> bool;
 
+    /// Check if the driver is installed, using the given [`VerificationLevel`].
+    /// Drivers for another [`target_platform`](WebdriverInstallationInfo::target_platform) can't be started,
+    /// so only [`VerificationLevel::Exists`] is checked for them.
+    async fn is_installed_with(&self, level: VerificationLevel) -> bool;
+
     /// Download, install driver. Skips verification process.
     async fn download_install(&self) -> Result<(), WebdriverDownloadError>;
 
     /// Download, verify, install driver.
+    /// Drivers for another [`target_platform`](WebdriverInstallationInfo::target_platform) can't be started,
+    /// so they are installed without verification, like [`download_install`](WebdriverDownloadInfo::download_install).
     async fn download_verify_install(&self, max_tries: usize)
         -> Result<(), WebdriverDownloadError>;
+
+    /// Lists what [`download_verify_install`](WebdriverDownloadInfo::download_verify_install) would do with `max_tries`,
+    /// without downloading or installing anything.
+    async fn plan(&self, max_tries: usize) -> Result<InstallationPlan, WebdriverDownloadError>;
+
+    /// Installs the driver if it is not installed, starts it and opens a session
+    /// with [`driver_capabilities`](WebdriverVerificationInfo::driver_capabilities).
+    ///
+    /// The returned [`DriverSession`] owns the driver process, which is shut down when the session is dropped.
+    /// Fails with [`WebdriverDownloadError::ForeignPlatform`] for drivers of another platform.
+    async fn ensure_session(
+        &self,
+        max_tries: usize,
+    ) -> Result<DriverSession, WebdriverDownloadError>;
+}
+
+/// Installation plan returned by [`WebdriverDownloadInfo::plan`].
+#[derive(Debug, Clone)]
+pub struct InstallationPlan {
+    pub browser_path: Option<PathBuf>,
+    pub browser_version: Option<Version>,
+    /// Candidate urls, in the order they would be tried.
+    pub version_urls: Vec<WebdriverVersionUrl>,
+    pub driver_install_path: PathBuf,
+}
+
+impl InstallationPlan {
+    /// The candidate that would be tried first, if any.
+    pub fn first_candidate(&self) -> Option<&WebdriverVersionUrl> {
+        self.version_urls.first()
+    }
 }
 
 /// Error that can occur when installing and verifying driver.
@@ -37,8 +81,14 @@ pub enum WebdriverDownloadError {
     Verify(#[from] VerificationError),
     #[error(transparent)]
     DefaultPath(#[from] DefaultPathError),
+    #[error(transparent)]
+    DriverProcess(#[from] DriverProcessError),
+    #[error("Failed to open session: {0}")]
+    Session(#[from] fantoccini::error::NewSessionError),
     #[error("Failed to move driver to driver_path: {0}")]
     Move(#[from] io::Error),
+    #[error("Driver for {target} cannot be started on {host}")]
+    ForeignPlatform { target: Platform, host: Platform },
     #[error("Tried {0} possible versions, but no version passed verification.")]
     NoVersionPassedVerification(usize),
     #[error(transparent)]
@@ -51,8 +101,21 @@ where
     T: WebdriverUrlInfo + WebdriverInstallationInfo + WebdriverVerificationInfo + Sync,
 {
     async fn is_installed(&self) -> bool {
+        self.is_installed_with(VerificationLevel::Full).await
+    }
+
+    async fn is_installed_with(&self, level: VerificationLevel) -> bool {
         let driver_path = self.driver_install_path();
-        self.verify_driver(&driver_path).await.is_ok()
+        let level = match self.target_platform() == Platform::current() {
+            true => level,
+            false => VerificationLevel::Exists,
+        };
+
+        match level {
+            VerificationLevel::Exists => driver_path.is_file(),
+            VerificationLevel::Status => self.check_driver_status(driver_path).await.is_ok(),
+            VerificationLevel::Full => self.verify_driver(driver_path).await.is_ok(),
+        }
     }
 
     async fn download_install(&self) -> Result<(), WebdriverDownloadError> {
@@ -68,7 +131,7 @@ where
 
         let tempdir = TempDir::new()?;
 
-        let temp_driver_path = self.download_in_tempdir(version_url.url, &tempdir).await?;
+        let temp_driver_path = self.download_in_tempdir(&version_url.url, &tempdir).await?;
 
         self.install_driver(&temp_driver_path)?;
 
@@ -79,6 +142,15 @@ where
         &self,
         max_tries: usize,
     ) -> Result<(), WebdriverDownloadError> {
+        let target_platform = self.target_platform();
+        if target_platform != Platform::current() {
+            tracing::info!(
+                "Skipping verification, drivers for {} can't be started on this host.",
+                target_platform
+            );
+            return self.download_install().await;
+        }
+
         let version_urls = self.version_urls(max_tries).await?;
         let url_count = version_urls.len();
 
@@ -89,7 +161,7 @@ where
             );
             let tempdir = TempDir::new()?;
 
-            let temp_driver_path = self.download_in_tempdir(version_url.url, &tempdir).await?;
+            let temp_driver_path = self.download_in_tempdir(&version_url.url, &tempdir).await?;
 
             match self.verify_driver(&temp_driver_path).await {
                 Ok(_) => {
@@ -106,4 +178,42 @@ where
             url_count,
         ))
     }
+
+    async fn plan(&self, max_tries: usize) -> Result<InstallationPlan, WebdriverDownloadError> {
+        let version_urls = self.version_urls(max_tries).await?;
+
+        Ok(InstallationPlan {
+            browser_path: self.browser_path().map(PathBuf::from),
+            browser_version: self.browser_version(),
+            version_urls,
+            driver_install_path: self.driver_install_path().to_path_buf(),
+        })
+    }
+
+    async fn ensure_session(
+        &self,
+        max_tries: usize,
+    ) -> Result<DriverSession, WebdriverDownloadError> {
+        let target_platform = self.target_platform();
+        if target_platform != Platform::current() {
+            return Err(WebdriverDownloadError::ForeignPlatform {
+                target: target_platform,
+                host: Platform::current(),
+            });
+        }
+
+        if !self.is_installed().await {
+            self.download_verify_install(max_tries).await?;
+        }
+
+        let driver = DriverProcess::start_with_config(
+            self.driver_install_path(),
+            None,
+            self.verification_config(),
+        )
+        .await?;
+        let client = driver.connect(self.driver_capabilities()).await?;
+
+        Ok(DriverSession::new(client, driver))
+    }
 }
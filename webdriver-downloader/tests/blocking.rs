@@ -0,0 +1,47 @@
+#![cfg(feature = "blocking")]
+
+use std::sync::{Arc, Mutex};
+
+use semver::Version;
+
+use common::MockWebdriverDownloadInfo;
+use webdriver_downloader::blocking::BlockingDriver;
+use webdriver_downloader::prelude::*;
+
+mod common;
+
+#[test]
+fn installs_without_runtime() {
+    let mut mock = MockWebdriverDownloadInfo::new();
+    mock.version_urls = Some(vec![WebdriverVersionUrl::new(
+        Default::default(),
+        Version::new(0, 0, 0),
+        "",
+    )]);
+    mock.download_in_tempdir = Some(Default::default());
+    mock.verify_driver = Arc::new(Mutex::new(vec![true]));
+    mock.install_driver = Arc::new(Mutex::new(vec![true]));
+
+    let driver = BlockingDriver::new(mock).unwrap();
+
+    driver.download_verify_install(1).unwrap();
+    assert!(driver.info().install_driver.lock().unwrap().is_empty());
+}
+
+#[test]
+fn reports_errors() {
+    let driver = BlockingDriver::new(MockWebdriverDownloadInfo::new()).unwrap();
+
+    assert!(driver.download_install().is_err());
+}
+
+#[test]
+#[should_panic]
+fn panics_inside_runtime() {
+    let driver = BlockingDriver::new(MockWebdriverDownloadInfo::new()).unwrap();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async { driver.download_install() }).ok();
+}
@@ -4,7 +4,6 @@ use std::sync::{Arc, Mutex};
 use anyhow::anyhow;
 use async_trait::async_trait;
 use fantoccini::wd::Capabilities;
-use reqwest::IntoUrl;
 use tempfile::TempDir;
 
 use webdriver_downloader::prelude::*;
@@ -19,6 +18,7 @@ pub struct MockWebdriverDownloadInfo<'a> {
     pub driver_executable_name: &'a str,
     pub download_in_tempdir: Option<PathBuf>,
     pub install_driver: Arc<Mutex<Vec<bool>>>,
+    pub target_platform: Platform,
 
     // verification_info
     pub driver_capabilities: Option<Capabilities>,
@@ -33,6 +33,7 @@ impl<'a> MockWebdriverDownloadInfo<'a> {
             driver_executable_name: Default::default(),
             download_in_tempdir: Default::default(),
             install_driver: Default::default(),
+            target_platform: Platform::current(),
             driver_capabilities: Default::default(),
             verify_driver: Default::default(),
         }
@@ -59,9 +60,9 @@ impl WebdriverInstallationInfo for MockWebdriverDownloadInfo<'_> {
         self.driver_executable_name
     }
 
-    async fn download_in_tempdir<U: IntoUrl + AsRef<str> + Send>(
+    async fn download_in_tempdir(
         &self,
-        _url: U,
+        _url: &str,
         _dir: &TempDir,
     ) -> Result<PathBuf, InstallationError> {
         self.download_in_tempdir
@@ -69,15 +70,16 @@ impl WebdriverInstallationInfo for MockWebdriverDownloadInfo<'_> {
             .ok_or(anyhow!("error").into())
     }
 
-    fn install_driver<P: AsRef<Path>>(
-        &self,
-        _temp_driver_path: &P,
-    ) -> Result<(), InstallationError> {
+    fn install_driver(&self, _temp_driver_path: &Path) -> Result<(), InstallationError> {
         match self.install_driver.lock().unwrap().remove(0) {
             true => Ok(()),
             false => Err(anyhow!("error").into()),
         }
     }
+
+    fn target_platform(&self) -> Platform {
+        self.target_platform
+    }
 }
 
 #[async_trait]
@@ -86,17 +88,18 @@ impl WebdriverVerificationInfo for MockWebdriverDownloadInfo<'_> {
         self.driver_capabilities.clone()
     }
 
-    async fn verify_driver<P: AsRef<Path> + Sync>(
-        &self,
-        _driver_path: &P,
-    ) -> Result<(), VerificationError> {
+    async fn verify_driver(&self, _driver_path: &Path) -> Result<(), VerificationError> {
         match self.verify_driver.lock().unwrap().remove(0) {
             true => Ok(()),
             false => Err(anyhow!("error").into()),
         }
     }
 
-    async fn test_client(_client: &fantoccini::Client) -> Result<(), VerificationError> {
+    async fn test_client(
+        &self,
+        _client: &fantoccini::Client,
+        _url: &str,
+    ) -> Result<(), VerificationError> {
         todo!()
     }
 }
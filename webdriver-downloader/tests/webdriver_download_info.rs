@@ -22,11 +22,7 @@ async fn passes_when_one_version_passes() -> Result<()> {
     let mut mock = MockWebdriverDownloadInfo::new();
     let version_count = 5;
 
-    let dummy_version_url = WebdriverVersionUrl {
-        version_req: Default::default(),
-        webdriver_version: Version::new(0, 0, 0),
-        url: Default::default(),
-    };
+    let dummy_version_url = WebdriverVersionUrl::new(Default::default(), Version::new(0, 0, 0), "");
     let urls = vec![dummy_version_url; version_count];
 
     mock.version_urls = Some(urls);
@@ -44,11 +40,7 @@ async fn fails_when_all_versions_fail() {
     let mut mock = MockWebdriverDownloadInfo::new();
     let version_count = 5;
 
-    let dummy_version_url = WebdriverVersionUrl {
-        version_req: Default::default(),
-        webdriver_version: Version::new(0, 0, 0),
-        url: Default::default(),
-    };
+    let dummy_version_url = WebdriverVersionUrl::new(Default::default(), Version::new(0, 0, 0), "");
     let urls = vec![dummy_version_url; version_count];
 
     mock.version_urls = Some(urls);
@@ -64,11 +56,7 @@ async fn fails_when_installation_fails() {
     let mut mock = MockWebdriverDownloadInfo::new();
     let version_count = 5;
 
-    let dummy_version_url = WebdriverVersionUrl {
-        version_req: Default::default(),
-        webdriver_version: Version::new(0, 0, 0),
-        url: Default::default(),
-    };
+    let dummy_version_url = WebdriverVersionUrl::new(Default::default(), Version::new(0, 0, 0), "");
     let urls = vec![dummy_version_url; version_count];
 
     mock.version_urls = Some(urls);
@@ -80,3 +68,109 @@ async fn fails_when_installation_fails() {
         .await
         .unwrap_err();
 }
+
+#[tokio::test]
+async fn plan_lists_candidates_in_order() -> Result<()> {
+    let mut mock = MockWebdriverDownloadInfo::new();
+    let install_path = std::path::Path::new("driver");
+    mock.driver_install_path = install_path;
+
+    let urls = (1..=3)
+        .rev()
+        .map(|major| {
+            WebdriverVersionUrl::new(
+                Default::default(),
+                Version::new(major, 0, 0),
+                format!("https://example.com/{}", major),
+            )
+        })
+        .collect::<Vec<_>>();
+    mock.version_urls = Some(urls.clone());
+
+    let plan = mock.plan(3).await?;
+
+    assert_eq!(plan.version_urls, urls);
+    assert_eq!(plan.first_candidate(), urls.first());
+    assert_eq!(plan.driver_install_path, install_path);
+    assert_eq!(plan.browser_path, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ensure_session_installs_before_starting_driver() {
+    let mut mock = MockWebdriverDownloadInfo::new();
+
+    let dummy_version_url = WebdriverVersionUrl::new(Default::default(), Version::new(0, 0, 0), "");
+
+    mock.version_urls = Some(vec![dummy_version_url]);
+    mock.download_in_tempdir = Some(Default::default());
+    // Not installed yet, then the downloaded driver passes verification.
+    mock.verify_driver = Arc::new(Mutex::new(vec![false, true]));
+    mock.install_driver = Arc::new(Mutex::new(vec![true]));
+
+    // The mock's install path is not a real driver, so starting it fails after installation.
+    let result = mock.ensure_session(1).await;
+
+    assert!(matches!(
+        result,
+        Err(WebdriverDownloadError::DriverProcess(_))
+    ));
+    assert!(mock.install_driver.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn installs_boxed_drivers() -> Result<()> {
+    let dummy_version_url = WebdriverVersionUrl::new(Default::default(), Version::new(0, 0, 0), "");
+
+    let mut passing = MockWebdriverDownloadInfo::new();
+    passing.version_urls = Some(vec![dummy_version_url.clone()]);
+    passing.download_in_tempdir = Some(Default::default());
+    passing.install_driver = Arc::new(Mutex::new(vec![true]));
+
+    let mut failing = MockWebdriverDownloadInfo::new();
+    failing.version_urls = Some(vec![dummy_version_url]);
+    failing.download_in_tempdir = Some(Default::default());
+    failing.install_driver = Arc::new(Mutex::new(vec![false]));
+
+    let drivers: Vec<Box<dyn WebdriverDownloadInfo>> = vec![Box::new(passing), Box::new(failing)];
+
+    let mut results = Vec::new();
+    for driver in &drivers {
+        results.push(driver.download_install().await.is_ok());
+    }
+
+    assert_eq!(results, [true, false]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn installs_foreign_platform_drivers_without_verification() -> Result<()> {
+    let mut mock = MockWebdriverDownloadInfo::new();
+    let foreign_platform = match Platform::current().os {
+        Os::Windows => Platform::new(Os::Linux, Arch::X86_64),
+        _ => Platform::new(Os::Windows, Arch::X86_64),
+    };
+
+    let dummy_version_url = WebdriverVersionUrl::new(Default::default(), Version::new(0, 0, 0), "");
+
+    mock.target_platform = foreign_platform;
+    mock.version_urls = Some(vec![dummy_version_url; 5]);
+    mock.download_in_tempdir = Some(Default::default());
+    mock.verify_driver = Arc::new(Mutex::new(vec![]));
+    mock.install_driver = Arc::new(Mutex::new(vec![true]));
+
+    mock.download_verify_install(5).await?;
+
+    // The driver can't be started, so only its existence is checked.
+    let installed = tempfile::NamedTempFile::new()?;
+    mock.driver_install_path = installed.path();
+    assert!(mock.is_installed_with(VerificationLevel::Full).await);
+    assert!(matches!(
+        mock.ensure_session(5).await,
+        Err(WebdriverDownloadError::ForeignPlatform { .. })
+    ));
+
+    Ok(())
+}